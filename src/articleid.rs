@@ -0,0 +1,146 @@
+//! AID: Article Identifier; LID: Location Identifier
+//!
+//! AID rows carry identifiers submitted by the publisher, each followed by a
+//! bracketed qualifier naming its kind, e.g. "10.1093/nargab/lqaa070 [doi]".
+//! LID rows use the same form for the electronic location of the article
+//! (DOI or pii); an unqualified LID is an e-locator used in place of pagination.
+//!
+//! Reference: https://www.nlm.nih.gov/bsd/mms/medlineelements.html#aid
+//! Reference: https://www.nlm.nih.gov/bsd/mms/medlineelements.html#lid
+
+use std::fmt;
+
+/// Kind of article identifier, from its bracketed qualifier
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum IdKind {
+    /// Digital Object Identifier
+    Doi,
+    /// Publisher Item Identifier
+    Pii,
+    /// PubMed Central identifier (as written in AID)
+    Pmc,
+    /// NIH manuscript identifier (e.g. NIHMS123456)
+    Mid,
+    /// NCBI Bookshelf accession (e.g. NBK1234)
+    BookAccession,
+    /// PubMed Central identifier
+    Pmcid,
+    /// Any other qualifier, as written
+    Other(String),
+}
+
+impl IdKind {
+    fn from_qualifier(q: &str) -> Self {
+        match q.to_ascii_lowercase().as_str() {
+            "doi" => Self::Doi,
+            "pii" => Self::Pii,
+            "pmc" => Self::Pmc,
+            "mid" => Self::Mid,
+            "bookaccession" => Self::BookAccession,
+            "pmcid" => Self::Pmcid,
+            _ => Self::Other(q.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Doi => "doi",
+            Self::Pii => "pii",
+            Self::Pmc => "pmc",
+            Self::Mid => "mid",
+            Self::BookAccession => "bookaccession",
+            Self::Pmcid => "pmcid",
+            Self::Other(q) => q,
+        }
+    }
+
+    /// Either spelling of a PubMed Central identifier
+    pub fn is_pmc(&self) -> bool {
+        matches!(self, Self::Pmc | Self::Pmcid)
+    }
+}
+
+impl fmt::Display for IdKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parsed AID or LID value
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ArticleId {
+    /// Identifier, trimmed, without its qualifier
+    pub value: String,
+
+    /// None when the row has no bracketed qualifier
+    pub kind: Option<IdKind>,
+}
+
+impl ArticleId {
+    /// Parse "value [qualifier]"; a value without a trailing bracketed
+    /// qualifier is kept whole, with no kind
+    pub fn parse(s: &str) -> Self {
+        let s = s.trim();
+        match s.strip_suffix(']').and_then(|s| s.rsplit_once('[')) {
+            Some((value, qualifier)) if !value.trim().is_empty() => ArticleId {
+                value: value.trim().to_string(),
+                kind: Some(IdKind::from_qualifier(qualifier.trim())),
+            },
+            _ => ArticleId {
+                value: s.to_string(),
+                kind: None,
+            },
+        }
+    }
+
+    pub fn is(&self, kind: &IdKind) -> bool {
+        self.kind.as_ref() == Some(kind)
+    }
+
+    /// Landing page for identifiers that have one at NCBI (PMC articles, Bookshelf)
+    pub fn url(&self) -> Option<String> {
+        match &self.kind {
+            Some(k) if k.is_pmc() => Some(format!("https://pmc.ncbi.nlm.nih.gov/articles/{}/", self.value)),
+            Some(IdKind::BookAccession) => Some(format!("https://www.ncbi.nlm.nih.gov/books/{}/", self.value)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ArticleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            Some(kind) => write!(f, "{} [{}]", self.value, kind),
+            None => f.write_str(&self.value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let doi = ArticleId::parse("10.1093/nargab/lqaa070 [doi]");
+        assert_eq!(doi.value, "10.1093/nargab/lqaa070");
+        assert_eq!(doi.kind, Some(IdKind::Doi));
+        assert_eq!(doi.to_string(), "10.1093/nargab/lqaa070 [doi]");
+
+        assert_eq!(ArticleId::parse("NBK1234 [bookaccession]").kind, Some(IdKind::BookAccession));
+        assert_eq!(ArticleId::parse("NIHMS123456 [mid]").kind, Some(IdKind::Mid));
+        assert_eq!(ArticleId::parse("S0140-6736(20)30183-5 [pii]").value, "S0140-6736(20)30183-5");
+        assert_eq!(ArticleId::parse("x [isbn]").kind, Some(IdKind::Other("isbn".into())));
+
+        let bare = ArticleId::parse("lqaa070");
+        assert_eq!(bare.value, "lqaa070");
+        assert_eq!(bare.kind, None);
+        assert_eq!(ArticleId::parse("[doi]").kind, None);
+
+        assert_eq!(
+            ArticleId::parse("PMC7531576 [pmc]").url().as_deref(),
+            Some("https://pmc.ncbi.nlm.nih.gov/articles/PMC7531576/")
+        );
+        assert_eq!(doi.url(), None);
+    }
+}
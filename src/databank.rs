@@ -0,0 +1,116 @@
+//! SI: Secondary Source ID
+//!
+//! Identifies a secondary source that supplies information, e.g., other data
+//! sources, databanks and accession numbers of molecular sequences discussed in
+//! articles, and clinical trial registrations, written "databank/accession",
+//! e.g. "ClinicalTrials.gov/NCT01234567", "GENBANK/AB123456".
+//!
+//! Reference: https://www.nlm.nih.gov/bsd/mms/medlineelements.html#si
+//! Reference: https://www.nlm.nih.gov/bsd/medline_databank_source.html
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+/// Parsed SI value
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SecondarySourceId {
+    /// Databank or registry, as written, e.g. "ClinicalTrials.gov"
+    pub databank: String,
+
+    /// Accession number within the databank, e.g. "NCT01234567"
+    pub accession: String,
+}
+
+/// Clinical trial registries among the NLM databanks
+const TRIAL_REGISTRIES: [&str; 3] = ["clinicaltrials.gov", "isrctn", "eudract"];
+
+impl SecondarySourceId {
+    /// Parse "databank/accession"; None without both parts
+    pub fn parse(value: &str) -> Option<Self> {
+        let (databank, accession) = value.split_once('/')?;
+        let (databank, accession) = (databank.trim(), accession.trim());
+        if databank.is_empty() || accession.is_empty() {
+            return None;
+        }
+        Some(SecondarySourceId {
+            databank: databank.to_string(),
+            accession: accession.to_string(),
+        })
+    }
+
+    /// Whether this is a clinical trial registration
+    pub fn is_trial_registration(&self) -> bool {
+        TRIAL_REGISTRIES.contains(&self.databank.to_lowercase().as_str())
+    }
+
+    /// Canonical URL of the record, for ClinicalTrials.gov, ISRCTN, EudraCT, GenBank and GEO
+    pub fn url(&self) -> Option<String> {
+        let acc = &self.accession;
+        match self.databank.to_lowercase().as_str() {
+            "clinicaltrials.gov" => Some(format!("https://clinicaltrials.gov/study/{}", acc)),
+            "isrctn" => {
+                let number = acc.strip_prefix("ISRCTN").unwrap_or(acc);
+                Some(format!("https://www.isrctn.com/ISRCTN{}", number))
+            },
+            "eudract" => Some(format!("https://www.clinicaltrialsregister.eu/ctr-search/search?query={}", acc)),
+            "genbank" => Some(format!("https://www.ncbi.nlm.nih.gov/nuccore/{}", acc)),
+            "geo" => Some(format!("https://www.ncbi.nlm.nih.gov/geo/query/acc.cgi?acc={}", acc)),
+            _ => None,
+        }
+    }
+}
+
+/// Serialized with its canonical `url`, where known, alongside databank and accession
+impl Serialize for SecondarySourceId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let url = self.url();
+        let mut map = serializer.serialize_map(Some(2 + url.is_some() as usize))?;
+        map.serialize_entry("databank", &self.databank)?;
+        map.serialize_entry("accession", &self.accession)?;
+        if let Some(url) = url {
+            map.serialize_entry("url", &url)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let nct = SecondarySourceId::parse("ClinicalTrials.gov/NCT01234567").unwrap();
+        assert_eq!(nct.databank, "ClinicalTrials.gov");
+        assert_eq!(nct.accession, "NCT01234567");
+        assert!(nct.is_trial_registration());
+        assert_eq!(nct.url().as_deref(), Some("https://clinicaltrials.gov/study/NCT01234567"));
+
+        let gb = SecondarySourceId::parse("GENBANK/AB123456").unwrap();
+        assert!(!gb.is_trial_registration());
+        assert_eq!(gb.url().as_deref(), Some("https://www.ncbi.nlm.nih.gov/nuccore/AB123456"));
+
+        let url = |v| SecondarySourceId::parse(v).unwrap().url();
+        assert_eq!(url("ISRCTN/ISRCTN12345678").as_deref(), Some("https://www.isrctn.com/ISRCTN12345678"));
+        assert_eq!(url("ISRCTN/12345678").as_deref(), Some("https://www.isrctn.com/ISRCTN12345678"));
+        assert_eq!(
+            url("EudraCT/2004-000123-45").as_deref(),
+            Some("https://www.clinicaltrialsregister.eu/ctr-search/search?query=2004-000123-45")
+        );
+        assert_eq!(
+            url("GEO/GSE12345").as_deref(),
+            Some("https://www.ncbi.nlm.nih.gov/geo/query/acc.cgi?acc=GSE12345")
+        );
+        assert_eq!(url("PDB/1ABC"), None);
+
+        assert_eq!(serde_json::to_value(&gb).unwrap(), serde_json::json!({
+            "databank": "GENBANK",
+            "accession": "AB123456",
+            "url": "https://www.ncbi.nlm.nih.gov/nuccore/AB123456",
+        }));
+        let pdb = SecondarySourceId::parse("PDB/1ABC").unwrap();
+        assert!(serde_json::to_value(&pdb).unwrap().get("url").is_none());
+
+        assert_eq!(SecondarySourceId::parse("NCT01234567"), None);
+        assert_eq!(SecondarySourceId::parse("GENBANK/"), None);
+    }
+}
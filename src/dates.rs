@@ -0,0 +1,244 @@
+use crate::types::DateParts;
+
+const MONTHS: [&str; 12] = [
+    "january", "february", "march", "april", "may", "june",
+    "july", "august", "september", "october", "november", "december",
+];
+
+/// CSL season numbering: 1 spring, 2 summer, 3 autumn, 4 winter
+const SEASONS: [(&str, u32); 5] = [
+    ("spring", 1), ("summer", 2), ("autumn", 3), ("fall", 3), ("winter", 4),
+];
+
+/// Month number from a MEDLINE month name ("Dec"; full names also accepted)
+fn month(word: &str) -> Option<u32> {
+    let word = word.to_lowercase();
+    if word.len() < 3 {
+        return None;
+    }
+    MONTHS.iter()
+        .position(|m| m.starts_with(&word))
+        .map(|i| i as u32 + 1)
+}
+
+fn season(word: &str) -> Option<u32> {
+    let word = word.to_lowercase();
+    SEASONS.iter().find(|(s, _)| *s == word).map(|(_, n)| *n)
+}
+
+fn year(word: &str) -> Option<u32> {
+    if word.len() == 4 && word.chars().all(|c| c.is_ascii_digit()) {
+        word.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Number of days in a month, accounting for leap years
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Day of the month; checked against the month only by the caller
+fn day(word: &str) -> Option<u32> {
+    if (1..=2).contains(&word.len()) && word.chars().all(|c| c.is_ascii_digit()) {
+        word.parse().ok().filter(|d| (1..=31).contains(d))
+    } else {
+        None
+    }
+}
+
+/// One side of a (possibly ranged) date
+///
+/// `inherit` holds the start of the range; leading components missing from the
+/// end of a range ("2020 Mar-Apr", "2020 Dec 1-15") are taken from it.
+/// Returns the date parts and season, if any
+fn parse_point(s: &str, inherit: Option<&[u32]>) -> Option<(Vec<u32>, Option<u32>)> {
+    let words: Vec<&str> = s.split_whitespace().collect();
+    let mut parts: Vec<u32> = Vec::new();
+    let mut rest = words.as_slice();
+
+    // year; at the end of a range it may be inherited
+    match rest.first().and_then(|w| year(w)) {
+        Some(y) => {
+            parts.push(y);
+            rest = &rest[1..];
+        }
+        None => parts.push(*inherit?.first()?),
+    }
+
+    // month, or season
+    if let Some(w) = rest.first() {
+        if let Some(m) = month(w) {
+            parts.push(m);
+            rest = &rest[1..];
+        } else if let Some(s) = season(w) {
+            return if rest.len() == 1 { Some((parts, Some(s))) } else { None };
+        } else if let (Some(m), Some(_)) = (inherit.and_then(|i| i.get(1)), day(w)) {
+            // bare day at the end of a range
+            if words.len() == 1 {
+                parts.push(*m);
+            }
+        }
+    }
+
+    // day
+    if let Some(w) = rest.first() {
+        if parts.len() != 2 {
+            return None;
+        }
+        let d = day(w).filter(|d| *d <= days_in_month(parts[0], parts[1]))?;
+        parts.push(d);
+        rest = &rest[1..];
+    }
+
+    if rest.is_empty() {
+        Some((parts, None))
+    } else {
+        None
+    }
+}
+
+/// Parse a MEDLINE date (e.g. the DP tag) into CSL date-parts
+///
+/// Recognized forms:
+///     "2020", "2020 Dec", "2020 Dec 5"
+///     "2020 Winter" (season)
+///     "2020 Mar-Apr", "2019 Dec-2020 Jan", "2020 Dec 1-15", "2020 Dec 28-2021 Jan 3"
+///
+/// Returns None for anything else; callers should fall back to a `raw` date
+pub fn parse_medline_date(value: &str) -> Option<DateParts> {
+    let (start, end) = match value.split_once('-') {
+        Some((start, end)) => (start, Some(end)),
+        None => (value, None),
+    };
+
+    let (start, season) = parse_point(start, None)?;
+    let mut date_parts = vec![start];
+    if let Some(end) = end {
+        if season.is_some() {
+            return None; // season ranges ("2020 Winter-Spring") have no CSL representation
+        }
+        let (end, end_season) = parse_point(end, Some(&date_parts[0]))?;
+        if end_season.is_some() || end.len() != date_parts[0].len() {
+            return None;
+        }
+        date_parts.push(end);
+    }
+
+    Some(DateParts {
+        date_parts: Some(date_parts),
+        season,
+        ..Default::default()
+    })
+}
+
+/// Parse a numeric MEDLINE date into CSL date-parts
+///
+/// Recognized forms:
+///     "20201002" (DEP, DCOM, LR)
+///     "2020/10/13 06:00", "2020/10/13" (EDAT, MHDA, CRDT, PHST); the time is dropped
+pub fn parse_numeric_date(value: &str) -> Option<DateParts> {
+    let date = value.split_whitespace().next()?;
+    let digits = date.chars().filter(|c| *c != '/').collect::<String>();
+    let slashes = date.len() - digits.len();
+    if digits.len() != 8 || !(slashes == 0 || slashes == 2) || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    if slashes == 2 && (date.as_bytes().get(4) != Some(&b'/') || date.as_bytes().get(7) != Some(&b'/')) {
+        return None;
+    }
+
+    let year = digits[0..4].parse().ok()?;
+    let month = digits[4..6].parse().ok().filter(|m| (1..=12).contains(m))?;
+    let day = digits[6..8].parse().ok().filter(|d| (1..=days_in_month(year, month)).contains(d))?;
+
+    Some(DateParts {
+        date_parts: Some(vec![vec![year, month, day]]),
+        ..Default::default()
+    })
+}
+
+/// Parse a PHST (publication history status) value, e.g. "2020/01/30 00:00 [received]",
+/// into its status ("received") and date
+pub fn parse_history(value: &str) -> Option<(&str, DateParts)> {
+    let (date, status) = value.trim_end().strip_suffix(']')?.rsplit_once('[')?;
+    Some((status.trim(), parse_numeric_date(date)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(value: &str) -> Option<Vec<Vec<u32>>> {
+        parse_medline_date(value).and_then(|dp| dp.date_parts)
+    }
+
+    #[test]
+    fn test_single() {
+        assert_eq!(parts("2020"), Some(vec![vec![2020]]));
+        assert_eq!(parts("2020 Dec"), Some(vec![vec![2020, 12]]));
+        assert_eq!(parts("2020 Dec 5"), Some(vec![vec![2020, 12, 5]]));
+        assert_eq!(parts("2020 December 05"), Some(vec![vec![2020, 12, 5]]));
+        assert_eq!(parts("2020 Feb 29"), Some(vec![vec![2020, 2, 29]]));
+        assert_eq!(parts("2000 Feb 29"), Some(vec![vec![2000, 2, 29]]));
+    }
+
+    #[test]
+    fn test_season() {
+        let dp = parse_medline_date("2020 Winter").unwrap();
+        assert_eq!(dp.date_parts, Some(vec![vec![2020]]));
+        assert_eq!(dp.season, Some(4));
+        assert_eq!(parse_medline_date("2019 Fall").unwrap().season, Some(3));
+        assert_eq!(parse_medline_date("2020 Winter-Spring"), None);
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert_eq!(parts("2019-2020"), Some(vec![vec![2019], vec![2020]]));
+        assert_eq!(parts("2020 Mar-Apr"), Some(vec![vec![2020, 3], vec![2020, 4]]));
+        assert_eq!(parts("2019 Dec-2020 Jan"), Some(vec![vec![2019, 12], vec![2020, 1]]));
+        assert_eq!(parts("2020 Dec 1-15"), Some(vec![vec![2020, 12, 1], vec![2020, 12, 15]]));
+        assert_eq!(parts("2020 Mar 30-Apr 2"), Some(vec![vec![2020, 3, 30], vec![2020, 4, 2]]));
+        assert_eq!(parts("2020 Dec 28-2021 Jan 3"), Some(vec![vec![2020, 12, 28], vec![2021, 1, 3]]));
+    }
+
+    #[test]
+    fn test_numeric() {
+        let ymd = Some(vec![vec![2020, 10, 2]]);
+        assert_eq!(parse_numeric_date("20201002").unwrap().date_parts, ymd);
+        assert_eq!(parse_numeric_date("2020/10/02 06:00").unwrap().date_parts, ymd);
+        assert_eq!(parse_numeric_date("2020/10/02").unwrap().date_parts, ymd);
+        assert_eq!(parse_numeric_date("2020/1002"), None);
+        assert_eq!(parse_numeric_date("2020102"), None);
+        assert_eq!(parse_numeric_date("20201302"), None);
+        assert_eq!(parse_numeric_date("2021/02/29"), None);
+        assert_eq!(parse_numeric_date("20200431"), None);
+        assert_eq!(parse_numeric_date("2020 Oct 2"), None);
+
+        let (status, dp) = parse_history("2020/01/30 00:00 [received]").unwrap();
+        assert_eq!(status, "received");
+        assert_eq!(dp.date_parts, Some(vec![vec![2020, 1, 30]]));
+        assert_eq!(parse_history("2020/01/30 00:00"), None);
+    }
+
+    #[test]
+    fn test_unparseable() {
+        assert_eq!(parts(""), None);
+        assert_eq!(parts("Dec 2020"), None);
+        assert_eq!(parts("2020 Dec 45"), None);
+        assert_eq!(parts("2020 Feb 31"), None);
+        assert_eq!(parts("2019 Feb 29"), None);
+        assert_eq!(parts("1900 Feb 29"), None);
+        assert_eq!(parts("2020 Apr 31"), None);
+        assert_eq!(parts("2021 Feb 27-30"), None);
+        assert_eq!(parts("2020 Dec 5 extra"), None);
+        assert_eq!(parts("2020 Dec-15"), None);
+        assert_eq!(parts("20201002"), None);
+    }
+}
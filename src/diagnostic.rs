@@ -0,0 +1,68 @@
+use std::fmt;
+
+/// How serious a diagnostic is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Input was repaired; output may differ from what the author intended
+    Warning,
+    /// Input could not be used and was dropped
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Source lines a diagnostic refers to
+///
+/// A merged (multi-line) row spans from its tagged line to its last continuation line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// 1-based ordinal of the record within the input
+    pub record: usize,
+
+    /// 1-based first source line
+    pub start_line: usize,
+
+    /// 1-based last source line (inclusive)
+    pub end_line: usize,
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start_line == self.end_line {
+            write!(f, "record {}, line {}", self.record, self.start_line)
+        } else {
+            write!(f, "record {}, lines {}-{}", self.record, self.start_line, self.end_line)
+        }
+    }
+}
+
+/// Problem found (and worked around) while reading in lenient mode
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub span: Span,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn warning(span: Span, message: String) -> Self {
+        Diagnostic { severity: Severity::Warning, span, message }
+    }
+
+    pub fn error(span: Span, message: String) -> Self {
+        Diagnostic { severity: Severity::Error, span, message }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.span, self.message)
+    }
+}
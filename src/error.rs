@@ -0,0 +1,203 @@
+use std::fmt;
+
+/// Position of a problem within the nbib input
+///
+/// Fields that are not known where the error is raised are left at their
+/// defaults (0 / empty / None) and filled in as the error travels back up
+/// through the reader.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Location {
+    /// 1-based ordinal of the record within the input
+    pub record: usize,
+
+    /// 1-based source line on which the offending row starts
+    pub line: usize,
+
+    /// Raw text of the offending row (continuation lines merged)
+    pub text: String,
+
+    /// MEDLINE/Pubmed tag of the offending row, if one could be read
+    pub tag: Option<String>,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.record > 0 {
+            write!(f, "record {}, ", self.record)?;
+        }
+        if self.line > 0 {
+            write!(f, "line {}, ", self.line)?;
+        }
+        if let Some(tag) = &self.tag {
+            write!(f, "tag {:?}, ", tag)?;
+        }
+        write!(f, "{:?}", self.text)
+    }
+}
+
+/// Errors produced while reading or converting nbib records
+#[derive(Debug)]
+pub enum Error {
+    /// Reading the underlying input failed
+    Io(std::io::Error),
+
+    /// Row does not have the `XXXX- value` layout
+    MalformedLine {
+        location: Location,
+        reason: &'static str,
+    },
+
+    /// Tag is not 1-4 characters
+    BadTag { location: Location },
+
+    /// Line is not valid UTF-8; `location.text` holds it with the bad bytes replaced
+    InvalidUtf8 { location: Location },
+
+    /// CSL-JSON serialization failed
+    Serialization(serde_json::Error),
+}
+
+impl Error {
+    /// Source location of the error, if it relates to a specific row
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            Self::MalformedLine { location, .. } => Some(location),
+            Self::BadTag { location } => Some(location),
+            Self::InvalidUtf8 { location } => Some(location),
+            Self::Io(_) | Self::Serialization(_) => None,
+        }
+    }
+
+    fn location_mut(&mut self) -> Option<&mut Location> {
+        match self {
+            Self::MalformedLine { location, .. } => Some(location),
+            Self::BadTag { location } => Some(location),
+            Self::InvalidUtf8 { location } => Some(location),
+            Self::Io(_) | Self::Serialization(_) => None,
+        }
+    }
+
+    /// Attach source line number and raw text, unless already known
+    pub(crate) fn at_line(mut self, line: usize, text: &str) -> Self {
+        if let Some(loc) = self.location_mut() {
+            if loc.line == 0 {
+                loc.line = line;
+            }
+            if loc.text.is_empty() {
+                loc.text = text.to_string();
+            }
+        }
+        self
+    }
+
+    /// Attach the record ordinal, unless already known
+    pub(crate) fn in_record(mut self, record: usize) -> Self {
+        if let Some(loc) = self.location_mut() {
+            if loc.record == 0 {
+                loc.record = record;
+            }
+        }
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "I/O error: {}", e),
+            Self::MalformedLine { location, reason } => {
+                write!(f, "Malformed line ({}) at {}", reason, location)
+            }
+            Self::BadTag { location } => write!(
+                f,
+                "MEDLINE/Pubmed nbib tags are 1-4 characters at {}",
+                location
+            ),
+            Self::InvalidUtf8 { location } => write!(f, "Invalid UTF-8 at {}", location),
+            Self::Serialization(e) => write!(f, "Serialization error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Serialization(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Serialization(e)
+    }
+}
+
+/// Failure to convert a single record
+///
+/// Yielded per record by `NbibReader` so that one corrupt record does not
+/// prevent the remaining records from being converted.
+#[derive(Debug)]
+pub struct RecordError {
+    /// 1-based ordinal of the record within the input
+    pub record: usize,
+
+    /// PMID of the record, if one could be read
+    pub pmid: Option<String>,
+
+    pub error: Error,
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.pmid {
+            Some(pmid) => write!(f, "record {} (PMID {}): {}", self.record, pmid, self.error),
+            None => write!(f, "record {}: {}", self.record, self.error),
+        }
+    }
+}
+
+impl std::error::Error for RecordError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<RecordError> for Error {
+    fn from(e: RecordError) -> Self {
+        e.error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context() {
+        let e = Error::BadTag {
+            location: Location {
+                tag: Some("XYZZY".into()),
+                ..Default::default()
+            },
+        };
+        let e = e.at_line(17, "XYZZY- val").in_record(3);
+        let loc = e.location().unwrap();
+        assert_eq!(loc.record, 3);
+        assert_eq!(loc.line, 17);
+        assert_eq!(loc.text, "XYZZY- val");
+        assert_eq!(loc.tag.as_deref(), Some("XYZZY"));
+        assert_eq!(
+            e.to_string(),
+            r#"MEDLINE/Pubmed nbib tags are 1-4 characters at record 3, line 17, tag "XYZZY", "XYZZY- val""#
+        );
+    }
+}
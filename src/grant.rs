@@ -0,0 +1,87 @@
+//! GR: Grant Number
+//!
+//! Research grant or contract number, or both, that designates financial
+//! support by any agency, written "number/acronym/agency/country", e.g.
+//! "R01 CA183444/CA/NCI NIH HHS/United States". The acronym is only present
+//! for NIH institutes, and the number itself may contain slashes.
+//!
+//! Reference: https://www.nlm.nih.gov/bsd/mms/medlineelements.html#gr
+
+use serde::Serialize;
+
+/// Parsed GR value
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct Grant {
+    /// Grant or contract number, e.g. "R01 CA183444"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<String>,
+
+    /// NIH institute acronym, e.g. "CA"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acronym: Option<String>,
+
+    /// Funding agency, e.g. "NCI NIH HHS"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agency: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+}
+
+/// Two or three capital letters, as NIH institute acronyms (e.g. "CA", "GM")
+fn is_acronym(s: &str) -> bool {
+    (2..=3).contains(&s.len()) && s.chars().all(|c| c.is_ascii_uppercase())
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    let s = s.trim();
+    if s.is_empty() {
+        None
+    } else {
+        Some(s.to_string())
+    }
+}
+
+impl Grant {
+    /// Parse a GR value; components are taken from the right, as only the number may contain "/"
+    pub fn parse(value: &str) -> Self {
+        let mut parts = value.split('/').collect::<Vec<&str>>();
+        let mut grant = Grant::default();
+        if parts.len() >= 2 {
+            grant.country = parts.pop().and_then(non_empty);
+            grant.agency = parts.pop().and_then(non_empty);
+            if parts.len() >= 2 && parts.last().is_some_and(|a| is_acronym(a.trim())) {
+                grant.acronym = parts.pop().and_then(non_empty);
+            }
+        }
+        grant.number = non_empty(&parts.join("/"));
+        grant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Grant::parse("R01 CA183444/CA/NCI NIH HHS/United States"), Grant {
+            number: Some("R01 CA183444".into()),
+            acronym: Some("CA".into()),
+            agency: Some("NCI NIH HHS".into()),
+            country: Some("United States".into()),
+        });
+
+        let mrc = Grant::parse("MR/N00875X/1/Medical Research Council/United Kingdom");
+        assert_eq!(mrc.number.as_deref(), Some("MR/N00875X/1"));
+        assert_eq!(mrc.acronym, None);
+        assert_eq!(mrc.agency.as_deref(), Some("Medical Research Council"));
+
+        let wt = Grant::parse("Wellcome Trust/United Kingdom");
+        assert_eq!(wt.number, None);
+        assert_eq!(wt.agency.as_deref(), Some("Wellcome Trust"));
+        assert_eq!(wt.country.as_deref(), Some("United Kingdom"));
+
+        assert_eq!(Grant::parse("P30 CA016058").number.as_deref(), Some("P30 CA016058"));
+    }
+}
@@ -0,0 +1,105 @@
+//! LA: Language
+//!
+//! The language in which the article was published, as the three-letter
+//! ISO 639-2/B code used by NLM (e.g. "eng", "fre", "ger", "chi").
+//! CSL `language` expects a BCP 47 tag, which uses the two-letter ISO 639-1
+//! code where one exists (e.g. "en", "fr", "de", "zh").
+//!
+//! Reference: https://www.nlm.nih.gov/bsd/language_table.html
+//! Reference: https://www.rfc-editor.org/info/bcp47
+
+/// NLM language code (ISO 639-2/B) -> BCP 47 tag
+pub const LANGUAGES: &[(&str, &str)] = &[
+    ("afr", "af"),      // Afrikaans
+    ("alb", "sq"),      // Albanian
+    ("amh", "am"),      // Amharic
+    ("ara", "ar"),      // Arabic
+    ("arm", "hy"),      // Armenian
+    ("aze", "az"),      // Azerbaijani
+    ("ben", "bn"),      // Bengali
+    ("bos", "bs"),      // Bosnian
+    ("bul", "bg"),      // Bulgarian
+    ("cat", "ca"),      // Catalan
+    ("chi", "zh"),      // Chinese
+    ("cze", "cs"),      // Czech
+    ("dan", "da"),      // Danish
+    ("dut", "nl"),      // Dutch
+    ("eng", "en"),      // English
+    ("epo", "eo"),      // Esperanto
+    ("est", "et"),      // Estonian
+    ("fin", "fi"),      // Finnish
+    ("fre", "fr"),      // French
+    ("geo", "ka"),      // Georgian
+    ("ger", "de"),      // German
+    ("gla", "gd"),      // Scottish Gaelic
+    ("gre", "el"),      // Greek, Modern
+    ("heb", "he"),      // Hebrew
+    ("hin", "hi"),      // Hindi
+    ("hrv", "hr"),      // Croatian
+    ("hun", "hu"),      // Hungarian
+    ("ice", "is"),      // Icelandic
+    ("ind", "id"),      // Indonesian
+    ("ita", "it"),      // Italian
+    ("jpn", "ja"),      // Japanese
+    ("kin", "rw"),      // Kinyarwanda
+    ("kor", "ko"),      // Korean
+    ("lat", "la"),      // Latin
+    ("lav", "lv"),      // Latvian
+    ("lit", "lt"),      // Lithuanian
+    ("mac", "mk"),      // Macedonian
+    ("mal", "ml"),      // Malayalam
+    ("mao", "mi"),      // Maori
+    ("may", "ms"),      // Malay
+    ("mul", "mul"),     // Multiple languages
+    ("nor", "no"),      // Norwegian
+    ("per", "fa"),      // Persian
+    ("pol", "pl"),      // Polish
+    ("por", "pt"),      // Portuguese
+    ("pus", "ps"),      // Pushto
+    ("rum", "ro"),      // Romanian
+    ("rus", "ru"),      // Russian
+    ("san", "sa"),      // Sanskrit
+    ("scc", "sr"),      // Serbian (former code)
+    ("scr", "hr"),      // Croatian (former code)
+    ("slo", "sk"),      // Slovak
+    ("slv", "sl"),      // Slovenian
+    ("spa", "es"),      // Spanish
+    ("srp", "sr"),      // Serbian
+    ("swe", "sv"),      // Swedish
+    ("tha", "th"),      // Thai
+    ("tur", "tr"),      // Turkish
+    ("ukr", "uk"),      // Ukrainian
+    ("und", "und"),     // Undetermined
+    ("urd", "ur"),      // Urdu
+    ("vie", "vi"),      // Vietnamese
+    ("wel", "cy"),      // Welsh
+];
+
+/// BCP 47 tag for an NLM language code, if known
+pub fn bcp47(code: &str) -> Option<&'static str> {
+    let code = code.trim();
+    LANGUAGES.iter()
+        .find(|(nlm, _)| nlm.eq_ignore_ascii_case(code))
+        .map(|(_, tag)| *tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bcp47() {
+        assert_eq!(bcp47("eng"), Some("en"));
+        assert_eq!(bcp47("fre"), Some("fr"));
+        assert_eq!(bcp47("GER"), Some("de"));
+        assert_eq!(bcp47("chi"), Some("zh"));
+        assert_eq!(bcp47("und"), Some("und"));
+        assert_eq!(bcp47("xxx"), None);
+
+        // one entry per code
+        let mut codes = LANGUAGES.iter().map(|(nlm, _)| *nlm).collect::<Vec<&str>>();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), LANGUAGES.len());
+    }
+}
@@ -1,49 +1,71 @@
 //! Direct port of https://github.com/blachlylab/nbib/
-use std::io::{self, prelude::*, BufReader};
+use std::io::BufReader;
 
+pub mod articleid;
+pub mod databank;
+pub mod dates;
+pub mod diagnostic;
+pub mod error;
+pub mod grant;
+pub mod language;
+pub mod mesh;
+pub mod names;
+pub mod options;
+pub mod orcid;
+pub mod pubtypes;
+pub mod reader;
+pub mod record;
+pub mod relation;
 pub mod tags;
 pub mod transforms;
 pub mod types;
-mod groupby;
 
-pub fn nbib_to_csl_items(mut input: impl std::io::Read) -> Result<Vec<types::CSLItem>, String>
+pub use articleid::ArticleId;
+pub use diagnostic::Diagnostic;
+pub use error::{Error, RecordError};
+pub use options::{Keywords, MeshKeywords, Mode, Options, Schema};
+pub use reader::NbibReader;
+pub use record::MedlineRecord;
+pub use tags::Tag;
+
+pub fn nbib_to_csl_items(input: impl std::io::Read) -> Result<Vec<types::CSLItem>, Error>
+{
+    Ok(nbib_to_csl_results(input).collect::<Result<Vec<types::CSLItem>, RecordError>>()?)
+}
+
+/// Convert records one at a time; a record that fails yields a `RecordError`
+/// (with its PMID where available) without affecting the others
+pub fn nbib_to_csl_results(input: impl std::io::Read) -> impl Iterator<Item = Result<types::CSLItem, RecordError>>
 {
-    let mut buf = String::new();
-    input.read_to_string(&mut buf).map_err(|e| e.to_string())?;
-    let range = buf.lines()
-        .collect::<Vec<&str>>()
-        .split(|line| *line == "") // an iterator over groups of lines
-        .map(|sl| sl.iter().cloned())  // iterator over iterator
-        .map(transforms::merge_multiline_items)
-        .map(|x| x.into_iter())
-        .map(transforms::medline_to_csl)
-        .map(|x| x.collect::<Result<Vec<types::CSLValue>, String>>())
-        .collect::<Result<Vec<Vec<types::CSLValue>>, String>>()?;
-    let range = range.into_iter().map(|x| transforms::reduce_authors(x.into_iter()));
-
-    Ok(transforms::into_csl_items(range).collect())
+    NbibReader::new(BufReader::new(input))
 }
 
 
-pub fn nbib_to_csljson(mut input: impl std::io::Read) -> Result<String, String>
+/// Parse records one at a time into lossless `MedlineRecord`s, without converting to CSL
+pub fn nbib_to_records(input: impl std::io::Read) -> impl Iterator<Item = Result<MedlineRecord, RecordError>>
 {
-    let mut buf = String::new();
-    input.read_to_string(&mut buf).map_err(|e| e.to_string())?;
-    let range = buf.lines()
-        .collect::<Vec<&str>>()
-        .split(|line| *line == "") // an iterator over groups of lines
-        .map(|sl| sl.iter().cloned())  // iterator over iterator
-        .map(transforms::merge_multiline_items)
-        .map(|x| x.into_iter())
-        .map(transforms::medline_to_csl)
-        .map(|x| x.collect::<Result<Vec<types::CSLValue>, String>>())
-        .collect::<Result<Vec<Vec<types::CSLValue>>, String>>()?;
-    let range = range.into_iter().map(|x| transforms::reduce_authors(x.into_iter()));
-    let range = transforms::into_csl_items(range);
-    
-    serde_json::to_string(
-        &transforms::to_json(range).map_err(|e|e.to_string())?
-    ).map_err(|e|e.to_string())
+    NbibReader::new(BufReader::new(input)).records()
+}
+
+/// Lenient conversion: malformed rows are repaired or skipped rather than failing,
+/// and each problem is reported as a `Diagnostic` alongside the items
+///
+/// Only I/O errors are fatal
+pub fn nbib_to_csl_items_lenient(input: impl std::io::Read) -> Result<(Vec<types::CSLItem>, Vec<Diagnostic>), Error>
+{
+    let options = Options { mode: Mode::Lenient, ..Default::default() };
+    let mut reader = NbibReader::with_options(BufReader::new(input), options);
+    let items = reader.by_ref().collect::<Result<Vec<types::CSLItem>, RecordError>>()?;
+
+    Ok((items, reader.take_diagnostics()))
+}
+
+
+pub fn nbib_to_csljson(input: impl std::io::Read) -> Result<String, Error>
+{
+    let items = nbib_to_csl_items(input)?;
+
+    Ok(serde_json::to_string(&transforms::to_json(items.into_iter())?)?)
 }
 
 
@@ -53,7 +75,7 @@ mod tests {
 
     #[test]
     fn nil() {
-        let mut input = String::from(r#"PMID- 12345
+        let input = String::from(r#"PMID- 12345
 XY  - Unused field
 AB  - This is the abstract's first line
       and this is its second line;
@@ -75,7 +97,7 @@ let expected = r#"
                 "given":"Charles Thomas"
             }
         ],
-        "id":"nbib-16023367964268412416",
+        "id":"nbib-12858232502722938509",
         "note":"PMID: 12345"
     }
 ]"#;
@@ -87,7 +109,7 @@ let expected = r#"
     #[test]
     fn real_cite() {
         use std::fs::File;
-        use std::io::BufReader;
+        use std::io::{BufRead, BufReader};
         use std::path::PathBuf;
         let dir = env!("CARGO_MANIFEST_DIR");
         let f = File::open(PathBuf::from(dir).join("tests").join("fade.nbib")).unwrap();
@@ -96,5 +118,118 @@ let expected = r#"
         let e_json: serde_json::Value = serde_json::from_str(&exp).unwrap();
         assert!(nbib_to_csljson(f).unwrap() == serde_json::to_string(&e_json).unwrap());
     }
+
+    #[test]
+    fn bookshelf_cite() {
+        use std::fs::File;
+        use std::io::{BufRead, BufReader};
+        use std::path::PathBuf;
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let f = File::open(PathBuf::from(dir).join("tests").join("bookshelf.nbib")).unwrap();
+        let e_f = File::open(PathBuf::from(dir).join("tests").join("bookshelf.json")).unwrap();
+        let exp = BufReader::new(e_f).lines().map(|x| x.unwrap()).collect::<Vec<String>>().join("");
+        let e_json: serde_json::Value = serde_json::from_str(&exp).unwrap();
+        assert!(nbib_to_csljson(f).unwrap() == serde_json::to_string(&e_json).unwrap());
+    }
+
+    #[test]
+    fn real_record() {
+        use std::fs::File;
+        use std::path::PathBuf;
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let f = File::open(PathBuf::from(dir).join("tests").join("fade.nbib")).unwrap();
+        let recs = nbib_to_records(f).collect::<Result<Vec<MedlineRecord>, RecordError>>().unwrap();
+        assert_eq!(recs.len(), 1);
+        assert_eq!(recs[0].pmid(), Some("33043294"));
+        assert_eq!(recs[0].get("OWN"), Some("NLM"));
+        assert_eq!(recs[0].get("STAT"), Some("PubMed-not-MEDLINE"));
+        assert_eq!(recs[0].get("JID"), Some("101756213"));
+        assert_eq!(recs[0].get_all("PHST").count(), 6);
+        let (status, received) = recs[0].history().next().unwrap();
+        assert_eq!(status, "received");
+        assert_eq!(received.date_parts, Some(vec![vec![2020, 1, 30]]));
+        assert_eq!(recs[0].get_all("AD").count(), 7);
+    }
+
+    /// Every tag used in the real-world fixtures is a known element
+    #[test]
+    fn fixture_tags_known() {
+        use std::fs::File;
+        use std::path::PathBuf;
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests");
+        for name in ["fade.nbib", "bookshelf.nbib"] {
+            let f = File::open(dir.join(name)).unwrap();
+            for rec in nbib_to_records(f) {
+                for (tag, _) in rec.unwrap().iter() {
+                    assert!(!matches!(tag, Tag::Unknown(_)), "{}: unknown tag {}", name, tag);
+                }
+            }
+        }
+    }
+
+    /// Every file in tests/corpus is hostile input and must not panic. Apart from the few
+    /// that are odd but well-formed, each must fail in strict mode and be reported in lenient mode
+    #[test]
+    fn corpus_no_panic() {
+        use std::fs;
+        use std::path::PathBuf;
+        const WELL_FORMED: [&str; 5] = ["blank_lines", "crlf_mixed", "dash_continuation", "empty", "truncated"];
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("corpus");
+        let mut n = 0;
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_stem().unwrap().to_str().unwrap().to_string();
+            let bytes = fs::read(&path).unwrap();
+            let strict = nbib_to_csljson(bytes.as_slice());
+            let lenient = nbib_to_csl_items_lenient(bytes.as_slice());
+            let (_, diagnostics) = lenient.unwrap_or_else(|e| panic!("{}: lenient mode failed: {}", name, e));
+            if WELL_FORMED.contains(&name.as_str()) {
+                assert!(strict.is_ok(), "{}: {}", name, strict.unwrap_err());
+                assert!(diagnostics.is_empty(), "{}: {:?}", name, diagnostics);
+            } else {
+                assert!(strict.is_err(), "{}: malformed input accepted in strict mode", name);
+                assert!(!diagnostics.is_empty(), "{}: malformed input not reported in lenient mode", name);
+            }
+            n += 1;
+        }
+        assert!(n > WELL_FORMED.len());
+    }
+
+    /// Randomly mutated copies of a real record; conversion may fail, but must not panic
+    #[test]
+    fn mutations_no_panic() {
+        use std::path::PathBuf;
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("fade.nbib");
+        let original = std::fs::read(path).unwrap();
+
+        // xorshift; deterministic so that failures are reproducible
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut rand = move |n: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % n as u64) as usize
+        };
+        const INSERTS: &[u8] = b"- \n\r,\xff\xc3";
+
+        for _ in 0..500 {
+            let mut bytes = original.clone();
+            for _ in 0..1 + rand(8) {
+                let i = rand(bytes.len());
+                match rand(3) {
+                    0 => bytes[i] = INSERTS[rand(INSERTS.len())],
+                    1 => bytes.insert(i, INSERTS[rand(INSERTS.len())]),
+                    _ => {
+                        let end = (i + rand(64)).min(bytes.len());
+                        bytes.drain(i..end);
+                    }
+                }
+                if bytes.is_empty() {
+                    break;
+                }
+            }
+            let _ = nbib_to_csljson(bytes.as_slice());
+        }
+    }
 }
 
@@ -0,0 +1,102 @@
+//! MH: MeSH Terms
+//!
+//! NLM Medical Subject Headings controlled vocabulary, used to characterize the
+//! content of the article. Each heading is a descriptor, optionally followed by
+//! "/"-separated subheadings (qualifiers); an asterisk marks a major topic of
+//! the article, on the descriptor or on a qualifier, e.g. "Neoplasms/*genetics".
+//!
+//! Reference: https://www.nlm.nih.gov/bsd/mms/medlineelements.html#mh
+
+use std::fmt;
+
+/// MeSH subheading, e.g. "genetics" in "Neoplasms/*genetics"
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Qualifier {
+    pub name: String,
+
+    /// Marked with an asterisk: the descriptor/qualifier pair is a major topic
+    pub major: bool,
+}
+
+/// Parsed MH value
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MeshHeading {
+    pub descriptor: String,
+
+    /// Marked with an asterisk: the descriptor is a major topic
+    pub major: bool,
+
+    pub qualifiers: Vec<Qualifier>,
+}
+
+/// Strip a leading major-topic asterisk
+fn major(s: &str) -> (&str, bool) {
+    let s = s.trim();
+    match s.strip_prefix('*') {
+        Some(s) => (s.trim(), true),
+        None => (s, false),
+    }
+}
+
+impl MeshHeading {
+    /// Parse an MH value, e.g. "*DNA, Neoplasm/genetics/*metabolism"
+    pub fn parse(value: &str) -> Self {
+        let mut parts = value.split('/');
+        let (descriptor, is_major) = major(parts.next().unwrap_or_default());
+        let qualifiers = parts
+            .map(major)
+            .filter(|(name, _)| !name.is_empty())
+            .map(|(name, major)| Qualifier { name: name.to_string(), major })
+            .collect();
+
+        MeshHeading {
+            descriptor: descriptor.to_string(),
+            major: is_major,
+            qualifiers,
+        }
+    }
+
+    /// Whether the descriptor, or any of its qualifiers, is a major topic
+    pub fn is_major_topic(&self) -> bool {
+        self.major || self.qualifiers.iter().any(|q| q.major)
+    }
+}
+
+/// Written back in MEDLINE form
+impl fmt::Display for MeshHeading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let star = |major| if major { "*" } else { "" };
+        write!(f, "{}{}", star(self.major), self.descriptor)?;
+        for q in &self.qualifiers {
+            write!(f, "/{}{}", star(q.major), q.name)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let mh = MeshHeading::parse("Humans");
+        assert_eq!(mh.descriptor, "Humans");
+        assert!(!mh.major);
+        assert!(mh.qualifiers.is_empty());
+        assert!(!mh.is_major_topic());
+
+        let mh = MeshHeading::parse("*DNA, Neoplasm/genetics/*metabolism");
+        assert_eq!(mh.descriptor, "DNA, Neoplasm");
+        assert!(mh.major);
+        assert_eq!(mh.qualifiers, vec![
+            Qualifier { name: "genetics".into(), major: false },
+            Qualifier { name: "metabolism".into(), major: true },
+        ]);
+        assert_eq!(mh.to_string(), "*DNA, Neoplasm/genetics/*metabolism");
+
+        let mh = MeshHeading::parse("Neoplasms/*genetics");
+        assert!(!mh.major);
+        assert!(mh.is_major_topic());
+    }
+}
@@ -0,0 +1,191 @@
+//! Personal names: FAU/FED/FIR ("Family, Given[, Suffix]") and AU/ED/IR ("Family Initials [Suffix]")
+//!
+//! Reference: https://www.nlm.nih.gov/bsd/mms/medlineelements.html#fau
+//! Reference: https://citeproc-js.readthedocs.io/en/latest/csl-json/markup.html#name-fields
+
+use crate::types::NameParts;
+
+/// Lowercase particles that precede the family name ("van der Berg") or,
+/// in the full form, trail the given names ("Ludwig van")
+const PARTICLES: [&str; 20] = [
+    "van", "von", "de", "der", "den", "da", "das", "do", "dos", "du",
+    "di", "del", "della", "la", "le", "ten", "ter", "te", "zu", "af",
+];
+
+/// Generational suffixes, as written by NLM and in common variants
+const SUFFIXES: [&str; 10] = ["Jr", "Jr.", "Sr", "Sr.", "II", "III", "IV", "2nd", "3rd", "4th"];
+
+fn is_particle(word: &str) -> bool {
+    PARTICLES.contains(&word)
+}
+
+fn is_suffix(word: &str) -> bool {
+    SUFFIXES.contains(&word)
+}
+
+/// AU-form initials: all uppercase letters, e.g. "JS"
+fn is_initials(word: &str) -> bool {
+    !word.is_empty() && word.chars().all(char::is_uppercase)
+}
+
+fn join(words: &[&str]) -> Option<String> {
+    if words.is_empty() {
+        None
+    } else {
+        Some(words.join(" "))
+    }
+}
+
+/// Split leading particles from a family name: "van der Berg" -> ("van der", "Berg")
+///
+/// The family name proper keeps at least one word
+fn split_family(family: &str) -> (Option<String>, Option<String>) {
+    let words = family.split_whitespace().collect::<Vec<&str>>();
+    let n = words.iter()
+        .take(words.len().saturating_sub(1))
+        .take_while(|w| is_particle(w))
+        .count();
+    (join(&words[..n]), join(&words[n..]))
+}
+
+/// Parse a name in either MEDLINE form; a comma marks the full form
+pub fn parse_name(name: &str) -> NameParts {
+    if name.contains(',') {
+        parse_full_name(name)
+    } else {
+        parse_short_name(name)
+    }
+}
+
+/// Full form, e.g. "van der Berg, Jan", "Smith, John A, Jr", "Beethoven, Ludwig van"
+pub fn parse_full_name(name: &str) -> NameParts {
+    let mut parts = name.split(',').map(str::trim);
+    let family = parts.next().unwrap_or_default();
+    let given = parts.next().unwrap_or_default();
+    let rest = parts.collect::<Vec<&str>>().join(" ");
+
+    let mut given = given.split_whitespace().collect::<Vec<&str>>();
+    let mut suffix = rest.split_whitespace().collect::<Vec<&str>>();
+    if suffix.is_empty() && given.len() > 1 && given.last().is_some_and(|w| is_suffix(w)) {
+        suffix.extend(given.pop());
+    }
+    let mut dropping = Vec::new();
+    while given.len() > 1 && given.last().is_some_and(|w| is_particle(w)) {
+        dropping.extend(given.pop());
+    }
+    dropping.reverse();
+
+    let (non_dropping_particle, family) = split_family(family);
+    NameParts {
+        family,
+        given: join(&given),
+        dropping_particle: join(&dropping),
+        non_dropping_particle,
+        suffix: join(&suffix),
+        ..Default::default()
+    }
+}
+
+/// Abbreviated form, e.g. "Blachly JS", "van der Berg J", "Smith J Jr"
+///
+/// `given` is derived from the initials ("JS" -> "J S"); a name without
+/// initials is taken to be a family name alone
+pub fn parse_short_name(name: &str) -> NameParts {
+    let mut words = name.split_whitespace().collect::<Vec<&str>>();
+    let suffix = if words.len() > 2 && words.last().is_some_and(|w| is_suffix(w)) {
+        words.pop()
+    } else {
+        None
+    };
+    let initials = if words.len() > 1 && words.last().is_some_and(|w| is_initials(w)) {
+        words.pop()
+    } else {
+        None
+    };
+
+    let (non_dropping_particle, family) = split_family(&words.join(" "));
+    NameParts {
+        family,
+        given: initials.map(|i| i.chars().map(String::from).collect::<Vec<String>>().join(" ")),
+        non_dropping_particle,
+        suffix: suffix.map(String::from),
+        ..Default::default()
+    }
+}
+
+/// Initials of given names: "Esko A" -> "EA", "Jean-Pierre" -> "JP", "Xiao-ming" -> "XM"
+///
+/// Lowercase words (particles such as "de") have no initial in the AU form;
+/// each part of a hyphenated name does, whatever its case
+pub fn initials(given: &str) -> String {
+    given.split_whitespace()
+        .filter(|w| !w.starts_with(char::is_lowercase))
+        .flat_map(|w| w.split('-'))
+        .filter_map(|part| part.chars().next())
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn np(family: &str, given: Option<&str>) -> NameParts {
+        NameParts {
+            family: Some(family.into()),
+            given: given.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_full_name() {
+        assert_eq!(parse_name("Blachly, James S"), np("Blachly", Some("James S")));
+
+        let jr = parse_name("Smith, John A, Jr");
+        assert_eq!(jr.family.as_deref(), Some("Smith"));
+        assert_eq!(jr.given.as_deref(), Some("John A"));
+        assert_eq!(jr.suffix.as_deref(), Some("Jr"));
+        assert_eq!(parse_name("Smith, John A III").suffix.as_deref(), Some("III"));
+
+        let vdb = parse_name("van der Berg, Jan");
+        assert_eq!(vdb.non_dropping_particle.as_deref(), Some("van der"));
+        assert_eq!(vdb.family.as_deref(), Some("Berg"));
+        assert_eq!(vdb.given.as_deref(), Some("Jan"));
+
+        let lvb = parse_name("Beethoven, Ludwig van");
+        assert_eq!(lvb.given.as_deref(), Some("Ludwig"));
+        assert_eq!(lvb.dropping_particle.as_deref(), Some("van"));
+
+        // a lone particle-like word is a family name
+        assert_eq!(parse_name("De, Anirban").family.as_deref(), Some("De"));
+        assert_eq!(parse_name("da, Maria").family.as_deref(), Some("da"));
+    }
+
+    #[test]
+    fn test_short_name() {
+        assert_eq!(parse_name("Blachly JS"), np("Blachly", Some("J S")));
+        assert_eq!(parse_name("Madonna"), np("Madonna", None));
+        assert_eq!(parse_name("Smith II"), np("Smith", Some("I I")));
+
+        let jr = parse_name("Smith JA Jr");
+        assert_eq!(jr.given.as_deref(), Some("J A"));
+        assert_eq!(jr.suffix.as_deref(), Some("Jr"));
+        assert_eq!(parse_name("Smith J 3rd").suffix.as_deref(), Some("3rd"));
+
+        let vdb = parse_name("van der Berg J");
+        assert_eq!(vdb.non_dropping_particle.as_deref(), Some("van der"));
+        assert_eq!(vdb.family.as_deref(), Some("Berg"));
+        assert_eq!(vdb.given.as_deref(), Some("J"));
+
+        assert_eq!(parse_name("da Silva MA").non_dropping_particle.as_deref(), Some("da"));
+    }
+
+    #[test]
+    fn test_initials() {
+        assert_eq!(initials("Esko A"), "EA");
+        assert_eq!(initials("Jean-Pierre"), "JP");
+        assert_eq!(initials("Xiao-ming"), "XM");
+        assert_eq!(initials("Maria de Lourdes"), "ML");
+    }
+}
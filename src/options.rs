@@ -0,0 +1,108 @@
+use crate::types::FieldMerge;
+
+/// How to treat malformed input
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Any malformed row aborts conversion of its record with an `Error`
+    #[default]
+    Strict,
+
+    /// Malformed rows are repaired where possible, otherwise skipped;
+    /// either way a `Diagnostic` is recorded and conversion continues
+    Lenient,
+}
+
+/// Version of CSL-JSON to target
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Schema {
+    /// CSL 1.0.1: PMID and PMCID are written into `note`; ISSN is omitted;
+    /// the DEP date is the extension date `available-date`
+    ///
+    /// Output is not pure 1.0.1: the item-level `custom` object (grants, relations,
+    /// history dates, publication type, languages, ...) is a 1.0.2 feature and is
+    /// still written. 1.0.1 processors are expected to ignore it.
+    #[default]
+    Csl101,
+
+    /// CSL 1.0.2: native `PMID`, `PMCID`, `ISSN` and `available-date` variables;
+    /// ISSN-L is an extension field
+    Csl102,
+}
+
+/// Which MeSH headings (MH) go into the CSL `keyword` string
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MeshKeywords {
+    /// None
+    Omit,
+
+    /// Every descriptor, without qualifiers ("Neoplasms")
+    #[default]
+    Descriptors,
+
+    /// Descriptors of major-topic headings only
+    MajorTopics,
+
+    /// Every heading as written, with qualifiers and asterisks ("Neoplasms/*genetics")
+    Headings,
+}
+
+/// How the CSL `keyword` string is built
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keywords {
+    pub mesh: MeshKeywords,
+
+    /// Also include author keywords (OT), after the MeSH terms; off by default
+    /// so that the keyword string holds a single vocabulary
+    pub other_terms: bool,
+
+    /// Placed between keywords; "; " by default, as MeSH descriptors may contain commas
+    pub separator: String,
+}
+
+impl Default for Keywords {
+    fn default() -> Self {
+        Self {
+            mesh: MeshKeywords::default(),
+            other_terms: false,
+            separator: "; ".into(),
+        }
+    }
+}
+
+/// Conversion options
+///
+/// Construct with `Options::default()` and override fields as needed:
+/// `Options { mode: Mode::Lenient, ..Default::default() }`
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub mode: Mode,
+
+    /// Target CSL-JSON version
+    pub schema: Schema,
+
+    /// Emit names exactly as the CSL schema defines them, without per-name
+    /// extension data (e.g. affiliations) under a `custom` object
+    pub strict_csl: bool,
+
+    /// Policy for ordinary CSL fields with repeated keys (e.g. `note`)
+    pub merge: FieldMerge,
+
+    /// CSL name variable for investigators (FIR, IR); "contributor" by default
+    pub investigator_role: String,
+
+    /// Contents of the CSL `keyword` string
+    pub keywords: Keywords,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            mode: Mode::default(),
+            schema: Schema::default(),
+            strict_csl: false,
+            merge: FieldMerge::default(),
+            investigator_role: "contributor".into(),
+            keywords: Keywords::default(),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! AUID: Author Identifier
+//!
+//! In practice an ORCID, written "ORCID: 0000-0001-9357-369X" (sometimes as a URL),
+//! directly after the author it identifies.
+//!
+//! Reference: https://support.orcid.org/hc/en-us/articles/360006897674-Structure-of-the-ORCID-Identifier
+
+/// Normalize an AUID value to the `https://orcid.org/xxxx-xxxx-xxxx-xxxx` form
+///
+/// Accepts the bare identifier, with or without hyphens, an "ORCID:" prefix,
+/// or an orcid.org URL. Returns None for anything that is not an ORCID, or
+/// whose ISO 7064 check digit does not match.
+pub fn normalize_orcid(value: &str) -> Option<String> {
+    let mut id = value.trim();
+    if let Some((scheme, rest)) = id.split_once(':') {
+        if scheme.eq_ignore_ascii_case("orcid") {
+            id = rest.trim();
+        }
+    }
+    for prefix in ["https://", "http://"] {
+        id = id.strip_prefix(prefix).unwrap_or(id);
+    }
+    id = id.strip_prefix("orcid.org/").unwrap_or(id);
+
+    let chars = id.chars()
+        .filter(|c| *c != '-')
+        .map(|c| c.to_ascii_uppercase())
+        .collect::<Vec<char>>();
+    if chars.len() != 16
+        || !chars[..15].iter().all(char::is_ascii_digit)
+        || !(chars[15].is_ascii_digit() || chars[15] == 'X')
+    {
+        return None;
+    }
+    if check_digit(&chars[..15]) != chars[15] {
+        return None;
+    }
+
+    let digits = chars.iter().collect::<String>();
+    Some(format!(
+        "https://orcid.org/{}-{}-{}-{}",
+        &digits[0..4], &digits[4..8], &digits[8..12], &digits[12..16]
+    ))
+}
+
+/// ISO 7064 11,2 check character over the first 15 digits
+fn check_digit(digits: &[char]) -> char {
+    let total = digits.iter()
+        .filter_map(|c| c.to_digit(10))
+        .fold(0, |total, d| (total + d) * 2);
+    match (12 - total % 11) % 11 {
+        10 => 'X',
+        n => char::from_digit(n, 10).unwrap_or('0'),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orcid() {
+        let url = Some("https://orcid.org/0000-0001-9357-369X".to_string());
+        assert_eq!(normalize_orcid("ORCID: 0000-0001-9357-369X"), url);
+        assert_eq!(normalize_orcid("ORCID: https://orcid.org/0000-0001-9357-369x"), url);
+        assert_eq!(normalize_orcid("000000019357369X"), url);
+        assert_eq!(
+            normalize_orcid("0000-0002-1825-0097").as_deref(),
+            Some("https://orcid.org/0000-0002-1825-0097")
+        );
+
+        assert_eq!(normalize_orcid("ORCID: 0000-0001-9357-3691"), None);   // bad check digit
+        assert_eq!(normalize_orcid("ORCID: 0000-0001-9357"), None);
+        assert_eq!(normalize_orcid("ISNI: 0000000121032683"), None);
+        assert_eq!(normalize_orcid(""), None);
+    }
+}
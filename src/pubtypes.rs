@@ -0,0 +1,153 @@
+//! PT: Publication Type
+//!
+//! This field describes the type of material that the article represents;
+//! it characterizes the nature of the information or the manner in which
+//! it is conveyed (e.g., Review, Letter, Retracted Publication, Clinical Trial).
+//! Records may contain more than one Publication Type, which are listed in alphabetical order.
+//!
+//! Almost all citations have one of these four basic, most frequently used
+//! Publication Types applied to them: Journal Article, Letter, Editorial, News.
+//! One of the above four Publication Types is applied to more than 99% of
+//! all citations indexed for MEDLINE.
+//!
+//! Reference: https://www.nlm.nih.gov/mesh/pubtypes.html
+//! Reference: https://aurimasv.github.io/z2csl/typeMap.xml#map-journalArticle
+
+/// Precedence of a publication type when choosing the single CSL `type` of a record
+///
+/// A record has several PT rows (e.g. "Journal Article", "Review", "Preprint");
+/// the one with the highest precedence decides the CSL type, the first in
+/// record order winning ties.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Precedence {
+    /// Describes content (study design, article genre); published in a journal
+    Content,
+    /// "Journal Article" itself
+    Journal,
+    /// Describes the form of the work, which is not a journal article
+    Form,
+}
+
+use Precedence::*;
+
+/// MEDLINE publication type -> (CSL type, precedence)
+pub const PUBLICATION_TYPES: &[(&str, &str, Precedence)] = &[
+    // form of the work
+    ("Preprint", "article", Form),
+    ("Dataset", "dataset", Form),
+    ("Newspaper Article", "article-newspaper", Form),
+    ("Congress", "paper-conference", Form),
+    ("Technical Report", "report", Form),
+    ("Legislation", "legislation", Form),
+    ("Legal Case", "legal_case", Form),
+    ("Patient Education Handout", "pamphlet", Form),
+    ("Video-Audio Media", "motion_picture", Form),
+    ("Webcast", "broadcast", Form),
+    ("Dictionary", "entry-dictionary", Form),
+    ("Encyclopedia", "entry-encyclopedia", Form),
+
+    ("Journal Article", "article-journal", Journal),
+
+    // content published in journals
+    ("Review", "article-journal", Content),
+    ("Systematic Review", "article-journal", Content),
+    ("Scoping Review", "article-journal", Content),
+    ("Meta-Analysis", "article-journal", Content),
+    ("Letter", "article-journal", Content),
+    ("Editorial", "article-journal", Content),
+    ("Comment", "article-journal", Content),
+    ("News", "article-journal", Content),
+    ("Case Reports", "article-journal", Content),
+    ("Clinical Study", "article-journal", Content),
+    ("Clinical Trial", "article-journal", Content),
+    ("Clinical Trial, Phase I", "article-journal", Content),
+    ("Clinical Trial, Phase II", "article-journal", Content),
+    ("Clinical Trial, Phase III", "article-journal", Content),
+    ("Clinical Trial, Phase IV", "article-journal", Content),
+    ("Clinical Trial Protocol", "article-journal", Content),
+    ("Clinical Trial, Veterinary", "article-journal", Content),
+    ("Controlled Clinical Trial", "article-journal", Content),
+    ("Randomized Controlled Trial", "article-journal", Content),
+    ("Randomized Controlled Trial, Veterinary", "article-journal", Content),
+    ("Pragmatic Clinical Trial", "article-journal", Content),
+    ("Equivalence Trial", "article-journal", Content),
+    ("Adaptive Clinical Trial", "article-journal", Content),
+    ("Multicenter Study", "article-journal", Content),
+    ("Observational Study", "article-journal", Content),
+    ("Observational Study, Veterinary", "article-journal", Content),
+    ("Comparative Study", "article-journal", Content),
+    ("Evaluation Study", "article-journal", Content),
+    ("Validation Study", "article-journal", Content),
+    ("Twin Study", "article-journal", Content),
+    ("Practice Guideline", "article-journal", Content),
+    ("Guideline", "article-journal", Content),
+    ("Consensus Development Conference", "article-journal", Content),
+    ("Consensus Development Conference, NIH", "article-journal", Content),
+    ("Clinical Conference", "article-journal", Content),
+    ("Published Erratum", "article-journal", Content),
+    ("Retraction of Publication", "article-journal", Content),
+    ("Retracted Publication", "article-journal", Content),
+    ("Expression of Concern", "article-journal", Content),
+    ("Corrected and Republished Article", "article-journal", Content),
+    ("Duplicate Publication", "article-journal", Content),
+    ("Introductory Journal Article", "article-journal", Content),
+    ("Historical Article", "article-journal", Content),
+    ("Classical Article", "article-journal", Content),
+    ("Biography", "article-journal", Content),
+    ("Autobiography", "article-journal", Content),
+    ("Bibliography", "article-journal", Content),
+    ("Portrait", "article-journal", Content),
+    ("Interview", "article-journal", Content),
+    ("Lecture", "article-journal", Content),
+    ("Address", "article-journal", Content),
+    ("Festschrift", "article-journal", Content),
+    ("Government Publication", "article-journal", Content),
+    ("Overall", "article-journal", Content),
+    ("Interactive Tutorial", "article-journal", Content),
+    ("Scientific Integrity Review", "article-journal", Content),
+    ("Research Support, N.I.H., Extramural", "article-journal", Content),
+    ("Research Support, N.I.H., Intramural", "article-journal", Content),
+    ("Research Support, Non-U.S. Gov't", "article-journal", Content),
+    ("Research Support, U.S. Gov't, Non-P.H.S.", "article-journal", Content),
+    ("Research Support, U.S. Gov't, P.H.S.", "article-journal", Content),
+    ("Research Support, American Recovery and Reinvestment Act", "article-journal", Content),
+];
+
+/// CSL type and precedence of a single publication type, if known
+pub fn lookup(pt: &str) -> Option<(&'static str, Precedence)> {
+    PUBLICATION_TYPES.iter()
+        .find(|(name, _, _)| name.eq_ignore_ascii_case(pt))
+        .map(|(_, csl_type, precedence)| (*csl_type, *precedence))
+}
+
+/// Choose the single CSL type for a record from all of its publication types
+///
+/// Unknown publication types are treated as journal content; a record with
+/// no publication types at all has no CSL type
+pub fn csl_type<'a, I>(pts: I) -> Option<&'static str>
+where
+    I: Iterator<Item = &'a str>,
+{
+    pts.map(|pt| lookup(pt).unwrap_or(("article-journal", Content)))
+        .enumerate()
+        // highest precedence; earliest wins ties
+        .max_by_key(|(i, (_, precedence))| (*precedence, std::cmp::Reverse(*i)))
+        .map(|(_, (csl_type, _))| csl_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csl_type() {
+        assert_eq!(csl_type(["Journal Article"].into_iter()), Some("article-journal"));
+        assert_eq!(csl_type(["Journal Article", "Review"].into_iter()), Some("article-journal"));
+        assert_eq!(csl_type(["Journal Article", "Preprint"].into_iter()), Some("article"));
+        assert_eq!(csl_type(["Dataset"].into_iter()), Some("dataset"));
+        assert_eq!(csl_type(["Letter", "Comment"].into_iter()), Some("article-journal"));
+        assert_eq!(csl_type(["Some Future Type"].into_iter()), Some("article-journal"));
+        assert_eq!(csl_type(["Preprint", "Dataset"].into_iter()), Some("article"));
+        assert_eq!(csl_type(std::iter::empty()), None);
+    }
+}
@@ -0,0 +1,357 @@
+use crate::diagnostic::{Diagnostic, Span};
+use crate::error::{Error, Location, RecordError};
+use crate::options::{Mode, Options};
+use crate::record::MedlineRecord;
+use crate::tags::Tag;
+use crate::transforms::*;
+use crate::types::*;
+use std::io::BufRead;
+
+/// Streaming MEDLINE/Pubmed nbib reader
+///
+/// Records in an nbib file are separated by one or more blank lines; a line
+/// holding only whitespace does not end a record.
+/// `NbibReader` pulls lines from the underlying `BufRead` only until the end
+/// of the current record, so memory use is bounded by the largest record
+/// rather than by the size of the whole export.
+///
+/// Each record is parsed into a lossless `MedlineRecord`
+/// (`merge_multiline_items` -> `split_row`), which the `Iterator` implementation
+/// then converts with `MedlineRecord::to_csl`. Use `next_record` or `records`
+/// to work with the `MedlineRecord`s directly.
+///
+/// Records are yielded independently: a record that fails to convert -- including
+/// one that is not valid UTF-8 -- yields a `RecordError` (carrying its PMID where
+/// one could be read) and reading continues with the next record. Only an I/O
+/// error ends iteration.
+///
+/// In `Mode::Lenient`, malformed rows are repaired or skipped instead of failing
+/// the record; the problems are collected and available from `diagnostics()`.
+pub struct NbibReader<R: BufRead> {
+    input: R,
+    options: Options,
+    diagnostics: Vec<Diagnostic>,
+    buf: Vec<u8>,  // line buffer, reused across calls to `read_until`
+    line: usize,   // number of lines read so far
+    record: usize, // number of records read so far
+    done: bool,    // input exhausted (or errored); no more records
+}
+
+impl<R: BufRead> NbibReader<R> {
+    pub fn new(input: R) -> Self {
+        Self::with_options(input, Options::default())
+    }
+
+    pub fn with_options(input: R, options: Options) -> Self {
+        NbibReader {
+            input,
+            options,
+            diagnostics: Vec::new(),
+            buf: Vec::new(),
+            line: 0,
+            record: 0,
+            done: false,
+        }
+    }
+
+    /// Diagnostics recorded so far (lenient mode only)
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Take the diagnostics recorded so far, e.g. to report them as records stream past
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// Read and parse the next record, without converting it to CSL
+    pub fn next_record(&mut self) -> Option<Result<MedlineRecord, RecordError>> {
+        let (first_line, raw) = match self.read_record() {
+            Ok(rec) => rec?,
+            Err(e) => {
+                self.done = true;
+                return Some(Err(RecordError { record: self.record + 1, pmid: None, error: e.into() }));
+            }
+        };
+        self.record += 1;
+
+        let lines = match self.decode(first_line, raw) {
+            Ok(lines) => lines,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let mut rec = MedlineRecord::new();
+        for (offsets, row) in merge_multiline_items(lines.iter().map(String::as_str)).with_offsets() {
+            let span = Span {
+                record: self.record,
+                start_line: first_line + offsets.start,
+                end_line: first_line + offsets.end - 1,
+            };
+            let parsed = split_row(&row)
+                .map_err(|e| e.at_line(span.start_line, &row).in_record(span.record));
+
+            match parsed {
+                Ok((tag, value)) => rec.push(tag, value, span.start_line),
+                Err(error) if self.options.mode == Mode::Strict => {
+                    return Some(Err(RecordError { record: self.record, pmid: find_pmid(&lines), error }));
+                }
+                Err(e) => {
+                    if let Some((tag, value)) = self.recover(span, &row, e) {
+                        rec.push(tag, value, span.start_line);
+                    }
+                }
+            }
+        }
+
+        Some(Ok(rec))
+    }
+
+    /// Iterate over parsed records, without converting them to CSL
+    pub fn records(mut self) -> impl Iterator<Item = Result<MedlineRecord, RecordError>> {
+        std::iter::from_fn(move || self.next_record())
+    }
+
+    /// Lenient mode: repair the row if possible, otherwise skip it; record a diagnostic either way
+    fn recover(&mut self, span: Span, row: &str, e: Error) -> Option<(Tag, String)> {
+        let repaired = repair_row(row);
+        match repaired {
+            Some(_) => self.diagnostics.push(Diagnostic::warning(span, format!("{}; repaired", e))),
+            None => self.diagnostics.push(Diagnostic::error(span, format!("{}; skipped", e))),
+        }
+        repaired
+    }
+
+    /// Decode the raw lines of the current record as UTF-8
+    ///
+    /// A line that is not valid UTF-8 fails the record; the other records are unaffected.
+    /// In lenient mode the line is decoded lossily instead, with a warning.
+    fn decode(&mut self, first_line: usize, raw: Vec<Vec<u8>>) -> Result<Vec<String>, RecordError> {
+        let mut lines = Vec::with_capacity(raw.len());
+        let mut error = None;
+        for (offset, bytes) in raw.into_iter().enumerate() {
+            match String::from_utf8(bytes) {
+                Ok(line) => lines.push(line),
+                Err(e) => {
+                    let line = String::from_utf8_lossy(e.as_bytes()).into_owned();
+                    let location = Location {
+                        record: self.record,
+                        line: first_line + offset,
+                        text: line.clone(),
+                        tag: None,
+                    };
+                    let e = Error::InvalidUtf8 { location };
+                    if self.options.mode == Mode::Strict {
+                        error.get_or_insert(e);
+                    } else {
+                        let line_no = first_line + offset;
+                        let span = Span { record: self.record, start_line: line_no, end_line: line_no };
+                        self.diagnostics.push(Diagnostic::warning(span, format!("{}; bad bytes replaced", e)));
+                    }
+                    lines.push(line);
+                }
+            }
+        }
+
+        match error {
+            None => Ok(lines),
+            Some(error) => Err(RecordError { record: self.record, pmid: find_pmid(&lines), error }),
+        }
+    }
+
+    /// Collect the raw lines of the next record, skipping any leading blank lines
+    ///
+    /// Returns the 1-based line number of the first line along with the lines,
+    /// or `Ok(None)` once the input is exhausted. Lines are not decoded here, so
+    /// that bad bytes only affect the record they belong to.
+    fn read_record(&mut self) -> std::io::Result<Option<(usize, Vec<Vec<u8>>)>> {
+        let mut lines: Vec<Vec<u8>> = Vec::new();
+        let mut first = 0;
+        while !self.done {
+            self.buf.clear();
+            if self.input.read_until(b'\n', &mut self.buf)? == 0 {
+                self.done = true;
+                break;
+            }
+            self.line += 1;
+            let end = self.buf.iter().rposition(|b| !matches!(b, b'\n' | b'\r')).map_or(0, |i| i + 1);
+            let line = &self.buf[..end];
+            if lines.is_empty() && line.iter().all(u8::is_ascii_whitespace) {
+                continue; // blank lines before (or between) records
+            }
+            if line.is_empty() {
+                break;
+            }
+            if lines.is_empty() {
+                first = self.line;
+            }
+            lines.push(line.to_vec());
+        }
+
+        if lines.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some((first, lines)))
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for NbibReader<R> {
+    type Item = Result<CSLItem, RecordError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().map(|rec| rec.map(|rec| rec.to_csl_with(&self.options)))
+    }
+}
+
+/// Value of the PMID row, read directly from the raw lines of a record
+fn find_pmid(lines: &[String]) -> Option<String> {
+    lines.iter()
+        .find_map(|line| line.strip_prefix("PMID-"))
+        .map(|pmid| pmid.trim().to_string())
+        .filter(|pmid| !pmid.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records() {
+        let input = "\r\nPMID- 1\r\nTI  - First title\r\n      continued\r\n\r\n\r\nPMID- 2\nTI  - Second title\n";
+        let items = NbibReader::new(input.as_bytes())
+            .collect::<Result<Vec<CSLItem>, RecordError>>()
+            .unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].fields[1].value, "First title continued");
+        assert_eq!(items[1].fields[1].value, "Second title");
+    }
+
+    #[test]
+    fn test_whitespace_line() {
+        // a line of spaces is not a blank line; it does not end the record
+        let input = "PMID- 1\nTI  - Title\n   \nAB  - Abstract\n\nPMID- 2\n";
+        let items = NbibReader::new(input.as_bytes())
+            .collect::<Result<Vec<CSLItem>, RecordError>>()
+            .unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].fields.len(), 3);
+        assert_eq!(items[0].fields[1].value, "Title");
+        assert_eq!(items[0].fields[2].value, "Abstract");
+    }
+
+    #[test]
+    fn test_error_location() {
+        let input = "PMID- 1\nTI  - First title\n\nPMID- 2\nAB  - Abstract\n      continued\nTI  -\n";
+        let err = NbibReader::new(input.as_bytes())
+            .find_map(|x| x.err())
+            .unwrap();
+
+        assert_eq!(err.record, 2);
+        assert_eq!(err.pmid.as_deref(), Some("2"));
+        match err.error {
+            Error::MalformedLine { location, .. } => {
+                assert_eq!(location.record, 2);
+                assert_eq!(location.line, 7);
+                assert_eq!(location.text, "TI  -");
+                assert_eq!(location.tag.as_deref(), Some("TI"));
+            }
+            other => panic!("expected MalformedLine, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_malformed_continuation() {
+        // an unindented row is never a continuation, even when it cannot be parsed
+        let input = "PMID- 1\nABCDE- five char tag\n\nPMID- 2\nTI    Title without separator\n";
+        let results = NbibReader::new(input.as_bytes()).collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 2);
+        for (n, result) in results.iter().enumerate() {
+            let err = result.as_ref().unwrap_err();
+            let location = err.error.location().unwrap();
+            assert_eq!(location.record, n + 1);
+            assert_eq!(location.line, 3 * n + 2);
+        }
+
+        let options = Options { mode: Mode::Lenient, ..Default::default() };
+        let mut reader = NbibReader::with_options(input.as_bytes(), options);
+        let items = reader.by_ref().collect::<Result<Vec<CSLItem>, RecordError>>().unwrap();
+        assert_eq!(items[0].fields[0].value, "PMID: 1");
+        assert_eq!(items[1].fields[0].value, "PMID: 2");
+        assert_eq!(reader.diagnostics().len(), 2);
+    }
+
+    #[test]
+    fn test_lenient() {
+        use crate::diagnostic::Severity;
+        let input = "      stray continuation\nPMID-12345\nTI  - Title\n      continued\nAB\n";
+        let options = Options { mode: Mode::Lenient, ..Default::default() };
+        let mut reader = NbibReader::with_options(input.as_bytes(), options);
+        let items = reader.by_ref().collect::<Result<Vec<CSLItem>, RecordError>>().unwrap();
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].fields[0].value, "PMID: 12345");
+        assert_eq!(items[0].fields[1].value, "Title continued");
+
+        let diagnostics = reader.take_diagnostics();
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].span, Span { record: 1, start_line: 1, end_line: 1 });
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert_eq!(diagnostics[1].span, Span { record: 1, start_line: 2, end_line: 2 });
+        assert_eq!(diagnostics[2].severity, Severity::Error);
+        assert_eq!(diagnostics[2].span, Span { record: 1, start_line: 5, end_line: 5 });
+        assert!(reader.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_bad_record_continues() {
+        let input = "PMID- 1\nTI  - First\n\nPMID- 2\nTI\n\nPMID- 3\nTI  - Third\n";
+        let results = NbibReader::new(input.as_bytes()).collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        let err = results[1].as_ref().unwrap_err();
+        assert_eq!(err.record, 2);
+        assert_eq!(err.pmid.as_deref(), Some("2"));
+        assert_eq!(results[2].as_ref().unwrap().fields[1].value, "Third");
+    }
+
+    #[test]
+    fn test_invalid_utf8_lenient() {
+        use crate::diagnostic::Severity;
+        let input = b"PMID- 1\nTI  - Bad \xff byte\n\nPMID- 2\n";
+        let options = Options { mode: Mode::Lenient, ..Default::default() };
+        let mut reader = NbibReader::with_options(&input[..], options);
+        let items = reader.by_ref().collect::<Result<Vec<CSLItem>, RecordError>>().unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].fields[1].value, "Bad \u{fffd} byte");
+        let diagnostics = reader.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(diagnostics[0].span, Span { record: 1, start_line: 2, end_line: 2 });
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let input = b"PMID- 1\nTI  - First\n\nPMID- 2\nTI  - Bad \xff byte\n\nPMID- 3\nTI  - Third\n";
+        let results = NbibReader::new(&input[..]).collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        let err = results[1].as_ref().unwrap_err();
+        assert_eq!(err.record, 2);
+        assert_eq!(err.pmid.as_deref(), Some("2"));
+        match &err.error {
+            Error::InvalidUtf8 { location } => {
+                assert_eq!(location.line, 5);
+                assert_eq!(location.text, "TI  - Bad \u{fffd} byte");
+            }
+            other => panic!("expected InvalidUtf8, got {:?}", other),
+        }
+        assert_eq!(results[2].as_ref().unwrap().fields[1].value, "Third");
+    }
+}
@@ -0,0 +1,744 @@
+use crate::articleid::{ArticleId, IdKind};
+use crate::databank::SecondarySourceId;
+use crate::dates::parse_history;
+use crate::grant::Grant;
+use crate::language::bcp47;
+use crate::mesh::MeshHeading;
+use crate::options::{MeshKeywords, Options, Schema};
+use crate::orcid::normalize_orcid;
+use crate::pubtypes;
+use crate::relation::Relation;
+use crate::tags::*;
+use crate::transforms::*;
+use crate::types::*;
+
+/// One tagged row of a MEDLINE/Pubmed record, continuation lines merged
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub tag: Tag,
+
+    pub value: String,
+
+    /// 1-based source line the row starts on (0 if not read from a file)
+    pub line: usize,
+}
+
+/// Lossless MEDLINE/Pubmed record
+///
+/// Every tagged row of the record is kept, in input order, whether or not the
+/// CSL mapping makes use of it (e.g. OWN, STAT, JID, PHST). Conversion to CSL
+/// is a separate step: `to_csl`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MedlineRecord {
+    pub fields: Vec<Field>,
+}
+
+impl MedlineRecord {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a row
+    pub fn push(&mut self, tag: Tag, value: String, line: usize) {
+        self.fields.push(Field { tag, value, line });
+    }
+
+    /// Value of the first row with this tag
+    pub fn get(&self, tag: &str) -> Option<&str> {
+        self.fields.iter()
+            .find(|f| f.tag.as_str() == tag)
+            .map(|f| f.value.as_str())
+    }
+
+    /// Values of every row with this tag, in input order
+    pub fn get_all<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.fields.iter()
+            .filter(move |f| f.tag.as_str() == tag)
+            .map(|f| f.value.as_str())
+    }
+
+    /// `(tag, value)` pairs in input order
+    pub fn iter(&self) -> impl Iterator<Item = (&Tag, &str)> {
+        self.fields.iter().map(|f| (&f.tag, f.value.as_str()))
+    }
+
+    pub fn pmid(&self) -> Option<&str> {
+        self.get("PMID")
+    }
+
+    /// Publication history (PHST) as `(status, date)` pairs, e.g. `("received", 2020-01-30)`
+    pub fn history(&self) -> impl Iterator<Item = (&str, DateParts)> {
+        self.get_all("PHST").filter_map(parse_history)
+    }
+
+    /// ISSNs (IS) as `(issn, qualifier)`, e.g. `("2631-9268", Some("Electronic"))`
+    pub fn issns(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.get_all("IS").map(|v| {
+            match v.trim_end().strip_suffix(')').and_then(|v| v.rsplit_once('(')) {
+                Some((issn, qualifier)) => (issn.trim(), Some(qualifier.trim())),
+                None => (v.trim(), None),
+            }
+        })
+    }
+
+    /// Article identifiers (AID), e.g. DOI and pii
+    pub fn article_ids(&self) -> impl Iterator<Item = ArticleId> + '_ {
+        self.get_all("AID").map(ArticleId::parse)
+    }
+
+    /// Location identifiers (LID): DOI, pii, or an unqualified e-locator
+    pub fn location_ids(&self) -> impl Iterator<Item = ArticleId> + '_ {
+        self.get_all("LID").map(ArticleId::parse)
+    }
+
+    /// MeSH headings (MH)
+    pub fn mesh_headings(&self) -> impl Iterator<Item = MeshHeading> + '_ {
+        self.get_all("MH").map(MeshHeading::parse)
+    }
+
+    /// Author keywords (OT), kept apart from the MeSH vocabulary
+    pub fn other_terms(&self) -> impl Iterator<Item = &str> {
+        self.get_all("OT")
+    }
+
+    /// Grants (GR) supporting the work
+    pub fn grants(&self) -> impl Iterator<Item = Grant> + '_ {
+        self.get_all("GR").map(Grant::parse)
+    }
+
+    /// Databank accessions and trial registrations (SI) that parse as "databank/accession"
+    pub fn secondary_source_ids(&self) -> impl Iterator<Item = SecondarySourceId> + '_ {
+        self.get_all("SI").filter_map(SecondarySourceId::parse)
+    }
+
+    /// Clinical trial registrations (ClinicalTrials.gov, ISRCTN, EudraCT) among the SI rows
+    pub fn trial_registrations(&self) -> impl Iterator<Item = SecondarySourceId> + '_ {
+        self.secondary_source_ids().filter(SecondarySourceId::is_trial_registration)
+    }
+
+    /// Languages (LA), as the original NLM codes ("eng", "fre")
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.get_all("LA")
+    }
+
+    /// Links to related records (comments, errata, retractions, ...), in input order
+    pub fn relations(&self) -> impl Iterator<Item = Relation> + '_ {
+        self.fields.iter().filter_map(|f| Relation::parse(&f.tag, &f.value))
+    }
+
+    /// Whether the item itself has been retracted (in whole or in part)
+    /// or is the subject of an expression of concern
+    pub fn retraction_status(&self) -> Option<&'static str> {
+        let has = |tags: &[Tag]| self.fields.iter().any(|f| tags.contains(&f.tag));
+        let has_pt = |pt: &str| self.get_all("PT").any(|v| v.eq_ignore_ascii_case(pt));
+        if has(&[Tag::RetractionIn, Tag::RetractedRepublishedIn]) || has_pt("Retracted Publication") {
+            Some("retracted")
+        } else if has(&[Tag::PartialRetractionIn]) {
+            Some("partially retracted")
+        } else if has(&[Tag::ExpressionOfConcernIn]) {
+            Some("expression of concern")
+        } else {
+            None
+        }
+    }
+
+    /// Convert to a CSL item with default options
+    ///
+    /// Tags without a CSL mapping are dropped here (but remain in the record)
+    pub fn to_csl(&self) -> CSLItem {
+        self.to_csl_with(&Options::default())
+    }
+
+    /// Convert to a CSL item
+    pub fn to_csl_with(&self, options: &Options) -> CSLItem {
+        let mut values: Vec<CSLValue> = Vec::new();
+        // name that rows describing a person (AD, IRAD, AUID) attach to
+        let mut person: Option<usize> = None;
+        // full-form name (FAU, FED, FIR) not yet paired with its abbreviated form
+        let mut unpaired: Option<(usize, &Tag)> = None;
+        for f in &self.fields {
+            let mut v = tag_to_csl_schema(&f.tag, f.value.clone(), options.schema);
+            if let (Tag::FullInvestigator | Tag::Investigator, CSLValue::CSLNameField(name)) = (&f.tag, &mut v) {
+                name.key.clone_from(&options.investigator_role);
+            }
+            if is_name_tag(&f.tag) {
+                // positional pairing: an abbreviated form names the same person as the
+                // full form just before it, provided family name and initials agree
+                let paired = unpaired.take().filter(|(i, full_tag)| {
+                    full_form(&f.tag) == Some(*full_tag)
+                        && values[*i].np().is_some_and(|np| abbreviates(np, &f.value))
+                });
+                if let Some((i, _)) = paired {
+                    person = Some(i);
+                    continue;
+                }
+                person = v.is_name().then_some(values.len());
+                if full_form(&f.tag).is_none() {
+                    unpaired = person.map(|i| (i, &f.tag));
+                }
+            }
+            match (&f.tag, person.and_then(|i| values.get_mut(i))) {
+                (Tag::Affiliation | Tag::InvestigatorAffiliation, Some(CSLValue::CSLNameField(name))) => {
+                    name.np.custom.affiliation.push(f.value.clone());
+                },
+                (Tag::AuthorIdentifier, Some(CSLValue::CSLNameField(name))) => {
+                    if let Some(orcid) = normalize_orcid(&f.value) {
+                        name.np.custom.orcid = Some(orcid);
+                    }
+                },
+                _ if !matches!(v, CSLValue::None) => values.push(v),
+                _ => (),
+            }
+        }
+
+        let mut item = into_csl_item(values.into_iter());
+        if options.strict_csl {
+            for name in &mut item.names {
+                name.np.custom = NameExtensions::default();
+            }
+        }
+        item.merge = options.merge.clone();
+        self.add_publication_type(&mut item);
+        self.add_book(&mut item);
+        self.add_identifiers(&mut item);
+        self.add_keywords(&mut item, options);
+        self.add_grants(&mut item);
+        self.add_relations(&mut item);
+        self.add_databanks(&mut item);
+        self.add_languages(&mut item);
+        if options.schema == Schema::Csl102 {
+            self.add_issn(&mut item);
+        }
+        item
+    }
+
+    /// CSL `keyword`: MeSH terms, then author keywords, as configured by `Options::keywords`
+    fn add_keywords(&self, item: &mut CSLItem, options: &Options) {
+        let config = &options.keywords;
+        let mut keywords: Vec<String> = match config.mesh {
+            MeshKeywords::Omit => Vec::new(),
+            MeshKeywords::Descriptors => self.mesh_headings().map(|mh| mh.descriptor).collect(),
+            MeshKeywords::MajorTopics => self.mesh_headings()
+                .filter(MeshHeading::is_major_topic)
+                .map(|mh| mh.descriptor)
+                .collect(),
+            MeshKeywords::Headings => self.mesh_headings().map(|mh| mh.to_string()).collect(),
+        };
+        if config.other_terms {
+            keywords.extend(self.other_terms().map(String::from));
+        }
+
+        if !keywords.is_empty() {
+            item.fields.push(CSLOrdinaryField {
+                key: "keyword".into(),
+                value: keywords.join(&config.separator),
+            });
+        }
+    }
+
+    /// Funding has no CSL variable: grants go in the extension field "grants"
+    fn add_grants(&self, item: &mut CSLItem) {
+        let grants = self.grants().collect::<Vec<Grant>>();
+        if grants.is_empty() {
+            return;
+        }
+        item.push_custom("grants", grants);
+    }
+
+    /// Retracted items, and those with an expression of concern, are flagged in the
+    /// CSL `status` variable; relations go in the extension field "relations"
+    fn add_relations(&self, item: &mut CSLItem) {
+        if let Some(status) = self.retraction_status() {
+            item.fields.push(CSLOrdinaryField {
+                key: "status".into(),
+                value: status.into(),
+            });
+        }
+        let relations = self.relations().collect::<Vec<Relation>>();
+        if relations.is_empty() {
+            return;
+        }
+        item.push_custom("relations", relations);
+    }
+
+    /// SI accessions, with canonical URLs where known, in the extension field "databanks"
+    fn add_databanks(&self, item: &mut CSLItem) {
+        let databanks = self.secondary_source_ids().collect::<Vec<SecondarySourceId>>();
+        if databanks.is_empty() {
+            return;
+        }
+        item.push_custom("databanks", databanks);
+    }
+
+    /// CSL `language` holds a single tag (the first LA); a multilingual
+    /// article lists all of its languages in the extension field "languages"
+    fn add_languages(&self, item: &mut CSLItem) {
+        if self.languages().nth(1).is_none() {
+            return;
+        }
+        let languages = self.languages()
+            .map(|code| bcp47(code).unwrap_or(code))
+            .collect::<Vec<&str>>();
+        item.push_custom("languages", languages);
+    }
+
+    /// Identifiers beyond the AID DOI: the LID DOI when there is no AID DOI,
+    /// a PMC or Bookshelf `URL`, the Bookshelf accession as `number`, and the
+    /// LID e-locator as `page` when there is no pagination
+    fn add_identifiers(&self, item: &mut CSLItem) {
+        let mut push = |key: &str, value: String| item.fields.push(CSLOrdinaryField {
+            key: key.into(),
+            value,
+        });
+
+        if !self.article_ids().any(|id| id.is(&IdKind::Doi)) {
+            if let Some(doi) = self.location_ids().find(|id| id.is(&IdKind::Doi)) {
+                push("DOI", doi.value);
+            }
+        }
+
+        let url = self.article_ids()
+            .find(|id| id.kind.as_ref().is_some_and(IdKind::is_pmc))
+            .or_else(|| self.get("PMC").map(|pmc| ArticleId { value: pmc.into(), kind: Some(IdKind::Pmc) }))
+            .or_else(|| self.article_ids().find(|id| id.is(&IdKind::BookAccession)))
+            .and_then(|id| id.url());
+        if let Some(url) = url {
+            push("URL", url);
+        }
+
+        if let Some(nbk) = self.article_ids().find(|id| id.is(&IdKind::BookAccession)) {
+            push("number", nbk.value);
+        }
+
+        if self.get("PG").is_none() {
+            if let Some(elocator) = self.location_ids().find(|id| id.kind.is_none()) {
+                push("page", elocator.value);
+            }
+        }
+    }
+
+    /// CSL `ISSN` is the print or electronic ISSN, whichever comes first;
+    /// the linking ISSN (ISSN-L) has no CSL variable and becomes the extension field "ISSN-L"
+    fn add_issn(&self, item: &mut CSLItem) {
+        let issn = self.issns()
+            .find(|(_, q)| matches!(q, Some(q) if q.eq_ignore_ascii_case("Print") || q.eq_ignore_ascii_case("Electronic")))
+            .or_else(|| self.issns().find(|(_, q)| q.is_none()));
+        if let Some((issn, _)) = issn {
+            item.fields.push(CSLOrdinaryField {
+                key: "ISSN".into(),
+                value: issn.into(),
+            });
+        }
+        let linking = self.issns()
+            .find(|(_, q)| matches!(q, Some(q) if q.eq_ignore_ascii_case("Linking")));
+        if let Some((issn_l, _)) = linking {
+            item.push_custom("ISSN-L", issn_l);
+        }
+    }
+
+    /// Exactly one CSL "type": `book` or `chapter` for Bookshelf records,
+    /// otherwise chosen from all PT rows by precedence; every PT value is
+    /// also kept, as the extension field "publication-type"
+    fn add_publication_type(&self, item: &mut CSLItem) {
+        if let Some(csl_type) = self.book_type().or_else(|| pubtypes::csl_type(self.get_all("PT"))) {
+            item.fields.push(CSLOrdinaryField {
+                key: "type".into(),
+                value: csl_type.into(),
+            });
+        }
+        if self.get("PT").is_some() {
+            item.push_custom("publication-type", self.get_all("PT").collect::<Vec<&str>>());
+        }
+    }
+
+    /// Bookshelf records carry a book title (BTI); with an article title (TI)
+    /// the record is a chapter of that book
+    fn book_type(&self) -> Option<&'static str> {
+        self.get("BTI")?;
+        if self.get("TI").is_some() {
+            Some("chapter")
+        } else {
+            Some("book")
+        }
+    }
+
+    /// Book title, as the chapter's `container-title` or the book's `title`;
+    /// PL, which for journals is the country of publication, as `publisher-place`
+    fn add_book(&self, item: &mut CSLItem) {
+        let (Some(csl_type), Some(bti)) = (self.book_type(), self.get("BTI")) else {
+            return;
+        };
+        let key = if csl_type == "chapter" { "container-title" } else { "title" };
+        item.fields.push(CSLOrdinaryField {
+            key: key.into(),
+            value: bti.into(),
+        });
+        if let Some(pl) = self.get("PL") {
+            item.fields.push(CSLOrdinaryField {
+                key: "publisher-place".into(),
+                value: pl.into(),
+            });
+        }
+    }
+}
+
+/// Rows naming a person or organization; rows that follow (AD, IRAD, AUID) describe it
+fn is_name_tag(tag: &Tag) -> bool {
+    matches!(tag,
+        Tag::FullAuthor | Tag::Author | Tag::FullEditor | Tag::Editor |
+        Tag::FullInvestigator | Tag::Investigator | Tag::CorporateAuthor)
+}
+
+/// Full form of an abbreviated name tag: AU -> FAU, ED -> FED, IR -> FIR
+fn full_form(tag: &Tag) -> Option<&'static Tag> {
+    match tag {
+        Tag::Author => Some(&Tag::FullAuthor),
+        Tag::Editor => Some(&Tag::FullEditor),
+        Tag::Investigator => Some(&Tag::FullInvestigator),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::Keywords;
+
+    #[test]
+    fn test_lookup() {
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::Pmid, "12345".into(), 1);
+        rec.push(Tag::Owner, "NLM".into(), 2);
+        rec.push(Tag::MeshTerms, "Humans".into(), 3);
+        rec.push(Tag::MeshTerms, "*Neoplasms/genetics".into(), 4);
+
+        assert_eq!(rec.pmid(), Some("12345"));
+        assert_eq!(rec.get("OWN"), Some("NLM"));
+        assert_eq!(rec.get("STAT"), None);
+        assert_eq!(rec.get_all("MH").collect::<Vec<&str>>(), vec!["Humans", "*Neoplasms/genetics"]);
+
+        // OWN has no CSL mapping, but survives in the record; MH rows become one keyword field
+        let item = rec.to_csl();
+        assert_eq!(item.fields.len(), 2);
+        assert_eq!(rec.fields.len(), 4);
+    }
+
+    #[test]
+    fn test_schema() {
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::Pmid, "33043294".into(), 1);
+        rec.push(Tag::Issn, "2631-9268 (Electronic)".into(), 2);
+        rec.push(Tag::Issn, "2631-9268 (Linking)".into(), 3);
+        rec.push(Tag::PmcId, "PMC7531576".into(), 4);
+        rec.push(Tag::DateElectronicPublication, "20201002".into(), 5);
+
+        assert_eq!(rec.issns().last(), Some(("2631-9268", Some("Linking"))));
+
+        let json = serde_json::to_value(rec.to_csl()).unwrap();
+        assert_eq!(json["note"], "PMID: 33043294\nPMCID: PMC7531576");
+        assert!(json.get("PMID").is_none());
+        assert!(json.get("ISSN").is_none());
+        assert!(json.get("available-date").is_none());
+        assert_eq!(json["custom"]["available-date"]["date-parts"], serde_json::json!([[2020, 10, 2]]));
+
+        let options = Options { schema: Schema::Csl102, ..Default::default() };
+        let json = serde_json::to_value(rec.to_csl_with(&options)).unwrap();
+        assert_eq!(json["PMID"], "33043294");
+        assert_eq!(json["PMCID"], "PMC7531576");
+        assert_eq!(json["ISSN"], "2631-9268");
+        assert_eq!(json["custom"]["ISSN-L"], "2631-9268");
+        assert_eq!(json["available-date"]["date-parts"], serde_json::json!([[2020, 10, 2]]));
+        assert!(json.get("note").is_none());
+    }
+
+    #[test]
+    fn test_identifiers() {
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::Pmid, "33043294".into(), 1);
+        rec.push(Tag::LocationIdentifier, "10.1093/nargab/lqaa070 [doi]".into(), 2);
+        rec.push(Tag::LocationIdentifier, "lqaa070".into(), 3);
+        rec.push(Tag::ArticleIdentifier, "lqaa070 [pii]".into(), 4);
+        rec.push(Tag::ArticleIdentifier, "PMC7531576 [pmc]".into(), 5);
+
+        assert_eq!(rec.article_ids().count(), 2);
+        let json = serde_json::to_value(rec.to_csl()).unwrap();
+        assert_eq!(json["DOI"], "10.1093/nargab/lqaa070");
+        assert_eq!(json["URL"], "https://pmc.ncbi.nlm.nih.gov/articles/PMC7531576/");
+        assert_eq!(json["page"], "lqaa070");
+        assert!(json.get("number").is_none());
+
+        rec.push(Tag::Pagination, "1-10".into(), 6);
+        rec.push(Tag::ArticleIdentifier, "10.1000/other [doi]".into(), 7);
+        let json = serde_json::to_value(rec.to_csl()).unwrap();
+        assert_eq!(json["DOI"], "10.1000/other");
+        assert_eq!(json["page"], "1-10");
+
+        // a qualified LID (here a pii) is not an e-locator
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::LocationIdentifier, "S0140-6736(20)30183-5 [pii]".into(), 1);
+        let json = serde_json::to_value(rec.to_csl()).unwrap();
+        assert!(json.get("page").is_none());
+    }
+
+    #[test]
+    fn test_person_extensions() {
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::FullAuthor, "Gregory, Thomas".into(), 1);
+        rec.push(Tag::Author, "Gregory T".into(), 2);
+        rec.push(Tag::AuthorIdentifier, "ORCID: 0000-0001-9357-369X".into(), 2);
+        rec.push(Tag::Affiliation, "Ohio State University".into(), 3);
+        rec.push(Tag::Affiliation, "Columbus, OH".into(), 4);
+        rec.push(Tag::FullAuthor, "Ngankeu, Apollinaire".into(), 5);
+        rec.push(Tag::Author, "Ngankeu A".into(), 6);
+
+        let item = rec.to_csl();
+        assert_eq!(item.names.len(), 2);
+        assert_eq!(item.names[0].np.custom.affiliation, vec!["Ohio State University", "Columbus, OH"]);
+        assert!(item.names[1].np.custom.is_empty());
+        assert_eq!(item.names[0].np.custom.orcid.as_deref(), Some("https://orcid.org/0000-0001-9357-369X"));
+        let json = serde_json::to_value(&item).unwrap();
+        assert_eq!(json["author"][0]["custom"]["affiliation"][1], "Columbus, OH");
+        assert!(json["author"][1].get("custom").is_none());
+
+        let options = Options { strict_csl: true, ..Default::default() };
+        let json = serde_json::to_value(rec.to_csl_with(&options)).unwrap();
+        assert!(json["author"][0].get("custom").is_none());
+
+        // AU only
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::Author, "Gregory T".into(), 1);
+        rec.push(Tag::Affiliation, "Ohio State University".into(), 2);
+        rec.push(Tag::Investigator, "Byrd JC".into(), 3);
+        rec.push(Tag::InvestigatorAffiliation, "Columbus, OH".into(), 4);
+        let item = rec.to_csl();
+        assert_eq!(item.names.len(), 2);
+        assert_eq!(item.names[0].np.custom.affiliation, vec!["Ohio State University"]);
+        assert_eq!(item.names[1].np.custom.affiliation, vec!["Columbus, OH"]);
+    }
+
+    #[test]
+    fn test_name_pairing() {
+        let names = |rows: &[(Tag, &str)]| {
+            let mut rec = MedlineRecord::new();
+            for (tag, value) in rows {
+                rec.push(tag.clone(), value.to_string(), 0);
+            }
+            rec.to_csl().names.into_iter().map(|n| n.np).collect::<Vec<NameParts>>()
+        };
+
+        // same surname, adjacent: father and son stay distinct
+        let np = names(&[
+            (Tag::FullAuthor, "Smith, John A"), (Tag::Author, "Smith JA"),
+            (Tag::FullAuthor, "Smith, Robert"), (Tag::Author, "Smith R"),
+        ]);
+        assert_eq!(np.len(), 2);
+        assert_eq!(np[0].given.as_deref(), Some("John A"));
+        assert_eq!(np[1].given.as_deref(), Some("Robert"));
+
+        // older records abbreviate to at most two initials
+        assert_eq!(names(&[(Tag::FullAuthor, "Doe, Jane Mary Ann"), (Tag::Author, "Doe JM")]).len(), 1);
+        assert_eq!(names(&[(Tag::FullAuthor, "Dupont, Jean-Pierre"), (Tag::Author, "Dupont JP")]).len(), 1);
+        assert_eq!(names(&[(Tag::FullAuthor, "Li, Xiao-ming"), (Tag::Author, "Li XM")]).len(), 1);
+        assert_eq!(names(&[(Tag::FullAuthor, "Kim, Jong-il"), (Tag::Author, "Kim JI")]).len(), 1);
+
+        // initials disagree: not the same person
+        assert_eq!(names(&[(Tag::FullAuthor, "Smith, John"), (Tag::Author, "Smith R")]).len(), 2);
+
+        // AU only, and FAU only
+        assert_eq!(names(&[(Tag::Author, "Smith J"), (Tag::Author, "Smith R")]).len(), 2);
+        assert_eq!(names(&[(Tag::FullAuthor, "Smith, John"), (Tag::FullAuthor, "Smith, Robert")]).len(), 2);
+
+        // particles and suffixes
+        let np = names(&[
+            (Tag::FullAuthor, "van der Berg, Jan"), (Tag::Author, "van der Berg J"),
+            (Tag::FullAuthor, "Smith, John, Jr"), (Tag::Author, "Smith J Jr"),
+        ]);
+        assert_eq!(np.len(), 2);
+        assert_eq!(np[0].non_dropping_particle.as_deref(), Some("van der"));
+        assert_eq!(np[1].given.as_deref(), Some("John"));
+        assert_eq!(np[1].suffix.as_deref(), Some("Jr"));
+
+        // AU only: given from initials
+        assert_eq!(names(&[(Tag::Author, "Kautto EA")])[0].given.as_deref(), Some("E A"));
+
+        // an editor's abbreviation does not pair with an author
+        assert_eq!(names(&[(Tag::FullAuthor, "Smith, John"), (Tag::Editor, "Smith J")]).len(), 2);
+    }
+
+    #[test]
+    fn test_corporate_author() {
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::FullAuthor, "Smith, John".into(), 1);
+        rec.push(Tag::Author, "Smith J".into(), 2);
+        rec.push(Tag::CorporateAuthor, "COVID-19 Genomics UK (COG-UK) Consortium".into(), 3);
+        rec.push(Tag::FullAuthor, "Doe, Jane".into(), 4);
+        rec.push(Tag::Author, "Doe J".into(), 5);
+
+        let json = serde_json::to_value(rec.to_csl()).unwrap();
+        assert_eq!(json["author"][0]["family"], "Smith");
+        assert_eq!(json["author"][1]["literal"], "COVID-19 Genomics UK (COG-UK) Consortium");
+        assert_eq!(json["author"][2]["family"], "Doe");
+        assert_eq!(json["author"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_name_roles() {
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::FullEditor, "Adam, Margaret P".into(), 1);
+        rec.push(Tag::Editor, "Adam MP".into(), 2);
+        rec.push(Tag::FullAuthor, "Blachly, James S".into(), 3);
+        rec.push(Tag::Author, "Blachly JS".into(), 4);
+        rec.push(Tag::FullEditor, "Ardinger, Holly H".into(), 5);
+        rec.push(Tag::Editor, "Ardinger HH".into(), 6);
+        rec.push(Tag::FullInvestigator, "Byrd, John C".into(), 7);
+        rec.push(Tag::Investigator, "Byrd JC".into(), 8);
+        rec.push(Tag::FullInvestigator, "Woyach, Jennifer A".into(), 9);
+        rec.push(Tag::Investigator, "Woyach JA".into(), 10);
+
+        let json = serde_json::to_value(rec.to_csl()).unwrap();
+        assert_eq!(json["author"].as_array().unwrap().len(), 1);
+        assert_eq!(json["editor"][0]["family"], "Adam");
+        assert_eq!(json["editor"][1]["family"], "Ardinger");
+        assert_eq!(json["editor"].as_array().unwrap().len(), 2);
+        assert_eq!(json["contributor"][0]["family"], "Byrd");
+        assert_eq!(json["contributor"][1]["given"], "Jennifer A");
+
+        let options = Options { investigator_role: "collaborator".into(), ..Default::default() };
+        let json = serde_json::to_value(rec.to_csl_with(&options)).unwrap();
+        assert!(json.get("contributor").is_none());
+        assert_eq!(json["collaborator"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_keywords() {
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::MeshTerms, "Humans".into(), 1);
+        rec.push(Tag::MeshTerms, "*DNA, Neoplasm/genetics".into(), 2);
+        rec.push(Tag::MeshTerms, "Neoplasms/*genetics/metabolism".into(), 3);
+        rec.push(Tag::OtherTermOwner, "NOTNLM".into(), 4);
+        rec.push(Tag::OtherTerm, "sequencing artifacts".into(), 5);
+        rec.push(Tag::OtherTerm, "Humans".into(), 6);
+
+        let mh = rec.mesh_headings().collect::<Vec<MeshHeading>>();
+        assert_eq!(mh.len(), 3);
+        assert_eq!(mh[2].qualifiers[0].name, "genetics");
+        assert!(mh[2].qualifiers[0].major);
+        assert_eq!(rec.other_terms().collect::<Vec<&str>>(), vec!["sequencing artifacts", "Humans"]);
+
+        let keyword = |keywords: Keywords| {
+            let options = Options { keywords, ..Default::default() };
+            serde_json::to_value(rec.to_csl_with(&options)).unwrap()["keyword"].clone()
+        };
+        assert_eq!(keyword(Keywords::default()), "Humans; DNA, Neoplasm; Neoplasms");
+        // OT terms are appended as given, even where they repeat a MeSH term
+        assert_eq!(
+            keyword(Keywords { other_terms: true, ..Default::default() }),
+            "Humans; DNA, Neoplasm; Neoplasms; sequencing artifacts; Humans"
+        );
+        assert_eq!(
+            keyword(Keywords { mesh: MeshKeywords::MajorTopics, other_terms: false, ..Default::default() }),
+            "DNA, Neoplasm; Neoplasms"
+        );
+        assert_eq!(
+            keyword(Keywords { mesh: MeshKeywords::Headings, other_terms: false, separator: "\n".into() }),
+            "Humans\n*DNA, Neoplasm/genetics\nNeoplasms/*genetics/metabolism"
+        );
+        assert_eq!(
+            keyword(Keywords { mesh: MeshKeywords::Omit, other_terms: false, ..Default::default() }),
+            serde_json::Value::Null
+        );
+    }
+
+    #[test]
+    fn test_grants() {
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::GrantNumber, "R01 CA183444/CA/NCI NIH HHS/United States".into(), 1);
+        rec.push(Tag::GrantNumber, "Wellcome Trust/United Kingdom".into(), 2);
+
+        assert_eq!(rec.grants().next().unwrap().acronym.as_deref(), Some("CA"));
+        let json = serde_json::to_value(rec.to_csl()).unwrap();
+        assert_eq!(json["custom"]["grants"], serde_json::json!([
+            {"number": "R01 CA183444", "acronym": "CA", "agency": "NCI NIH HHS", "country": "United States"},
+            {"agency": "Wellcome Trust", "country": "United Kingdom"},
+        ]));
+    }
+
+    #[test]
+    fn test_relations() {
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::Pmid, "12345".into(), 1);
+        rec.push(Tag::CommentIn, "Nature. 2020 Oct;586(7829):E7. PMID: 33057194".into(), 2);
+        rec.push(Tag::ExpressionOfConcernIn, "J Foo. 2020;2:5. PMID: 222".into(), 3);
+
+        let relations = rec.relations().collect::<Vec<Relation>>();
+        assert_eq!(relations.len(), 2);
+        assert_eq!(relations[1].kind, Tag::ExpressionOfConcernIn);
+        assert_eq!(rec.retraction_status(), Some("expression of concern"));
+
+        rec.push(Tag::RetractionIn, "Retraction in: J Foo. 2021;3:1. PMID: 123".into(), 4);
+        let json = serde_json::to_value(rec.to_csl()).unwrap();
+        assert_eq!(json["status"], "retracted");
+        assert_eq!(json["custom"]["relations"][2], serde_json::json!(
+            {"kind": "RIN", "citation": "J Foo. 2021;3:1.", "pmid": "123"}
+        ));
+
+        // the retraction notice itself is not retracted
+        let mut notice = MedlineRecord::new();
+        notice.push(Tag::RetractionOf, "J Foo. 2019;1:1. PMID: 12345".into(), 1);
+        assert_eq!(notice.retraction_status(), None);
+        assert!(serde_json::to_value(notice.to_csl()).unwrap().get("status").is_none());
+    }
+
+    #[test]
+    fn test_databanks() {
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::SecondarySourceId, "ClinicalTrials.gov/NCT01234567".into(), 1);
+        rec.push(Tag::SecondarySourceId, "GENBANK/AB123456".into(), 2);
+        rec.push(Tag::SecondarySourceId, "unstructured".into(), 3);
+
+        assert_eq!(rec.secondary_source_ids().count(), 2);
+        let trials = rec.trial_registrations().collect::<Vec<SecondarySourceId>>();
+        assert_eq!(trials.len(), 1);
+        assert_eq!(trials[0].accession, "NCT01234567");
+
+        let json = serde_json::to_value(rec.to_csl()).unwrap();
+        assert_eq!(json["custom"]["databanks"][0], serde_json::json!({
+            "databank": "ClinicalTrials.gov",
+            "accession": "NCT01234567",
+            "url": "https://clinicaltrials.gov/study/NCT01234567",
+        }));
+        assert_eq!(json["custom"]["databanks"][1]["url"], "https://www.ncbi.nlm.nih.gov/nuccore/AB123456");
+    }
+
+    #[test]
+    fn test_languages() {
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::Language, "eng".into(), 1);
+        let json = serde_json::to_value(rec.to_csl()).unwrap();
+        assert_eq!(json["language"], "en");
+        assert!(json.get("custom").is_none());
+
+        rec.push(Tag::Language, "fre".into(), 2);
+        rec.push(Tag::Language, "xyz".into(), 3);
+        assert_eq!(rec.languages().collect::<Vec<&str>>(), vec!["eng", "fre", "xyz"]);
+        let json = serde_json::to_value(rec.to_csl()).unwrap();
+        assert_eq!(json["language"], "en");
+        assert_eq!(json["custom"]["languages"], serde_json::json!(["en", "fr", "xyz"]));
+    }
+
+    #[test]
+    fn test_publication_type() {
+        let mut rec = MedlineRecord::new();
+        rec.push(Tag::Pmid, "12345".into(), 1);
+        rec.push(Tag::PublicationType, "Journal Article".into(), 2);
+        rec.push(Tag::PublicationType, "Preprint".into(), 3);
+        rec.push(Tag::PublicationType, "Review".into(), 4);
+
+        let item = rec.to_csl();
+        let types = item.fields.iter().filter(|f| f.key == "type").collect::<Vec<_>>();
+        assert_eq!(types.len(), 1);
+        assert_eq!(types[0].value, "article");
+        assert_eq!(item.custom[0].key, "publication-type");
+        assert_eq!(item.custom[0].value, serde_json::json!(["Journal Article", "Preprint", "Review"]));
+    }
+}
@@ -0,0 +1,120 @@
+//! Links to related records: comments, errata, retractions, updates, republications, datasets
+//!
+//! Each relation tag (CIN, CON, EIN, EFR, RIN, ROF, UOF, ...) holds a citation
+//! of the related record, usually ending in its PMID, e.g.
+//! "RIN - J Foo. 2021;3:1. PMID: 12345678". Some sources repeat the tag's
+//! description as a prefix ("Retraction in: J Foo. ...").
+//!
+//! Reference: https://www.nlm.nih.gov/bsd/mms/medlineelements.html#cin
+
+use crate::tags::Tag;
+use serde::Serialize;
+
+/// Tags that point to another record
+pub const RELATION_TAGS: [Tag; 22] = [
+    Tag::CommentIn,
+    Tag::CommentOn,
+    Tag::CorrectedRepublishedFrom,
+    Tag::CorrectedRepublishedIn,
+    Tag::DatasetDescribedIn,
+    Tag::DatasetUseReportedIn,
+    Tag::ExpressionOfConcernFor,
+    Tag::ExpressionOfConcernIn,
+    Tag::ErratumFor,
+    Tag::ErratumIn,
+    Tag::OriginalReportIn,
+    Tag::PartialRetractionIn,
+    Tag::PartialRetractionOf,
+    Tag::RetractionIn,
+    Tag::RetractionOf,
+    Tag::RepublishedFrom,
+    Tag::RepublishedIn,
+    Tag::RetractedRepublishedFrom,
+    Tag::RetractedRepublishedIn,
+    Tag::SummaryForPatientsIn,
+    Tag::UpdateIn,
+    Tag::UpdateOf,
+];
+
+pub fn is_relation(tag: &Tag) -> bool {
+    RELATION_TAGS.contains(tag)
+}
+
+/// Parsed relation row
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct Relation {
+    /// The relation tag, e.g. `Tag::RetractionIn`; serialized as its code, "RIN"
+    pub kind: Tag,
+
+    /// Citation of the related record, without its PMID
+    #[serde(rename = "citation")]
+    pub citation_text: String,
+
+    /// PMID of the related record, when given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pmid: Option<String>,
+}
+
+impl Relation {
+    /// Parse the value of a relation tag; None for any other tag
+    pub fn parse(kind: &Tag, value: &str) -> Option<Self> {
+        if !is_relation(kind) {
+            return None;
+        }
+        let mut text = value.trim();
+
+        // optional "<description>:" prefix
+        if let Some((prefix, rest)) = text.split_once(':') {
+            if prefix.trim().eq_ignore_ascii_case(kind.description()) {
+                text = rest.trim();
+            }
+        }
+
+        let mut pmid = None;
+        if let Some((citation, id)) = text.rsplit_once("PMID:") {
+            let id = id.trim().trim_end_matches('.');
+            if !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) {
+                pmid = Some(id.to_string());
+                text = citation.trim();
+            }
+        }
+
+        Some(Relation {
+            kind: kind.clone(),
+            citation_text: text.to_string(),
+            pmid,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let rin = Relation::parse(&Tag::RetractionIn, "Retraction in: J Foo. 2021;3:1. PMID: 123").unwrap();
+        assert_eq!(rin.kind, Tag::RetractionIn);
+        assert_eq!(rin.citation_text, "J Foo. 2021;3:1.");
+        assert_eq!(rin.pmid.as_deref(), Some("123"));
+
+        let cin = Relation::parse(&Tag::CommentIn, "Nature. 2020 Oct;586(7829):E7. PMID: 33057194").unwrap();
+        assert_eq!(cin.citation_text, "Nature. 2020 Oct;586(7829):E7.");
+        assert_eq!(cin.pmid.as_deref(), Some("33057194"));
+
+        // no PMID; a colon in the citation is not a prefix
+        let ein = Relation::parse(&Tag::ErratumIn, "Blood: 2021;137(4):572").unwrap();
+        assert_eq!(ein.citation_text, "Blood: 2021;137(4):572");
+        assert_eq!(ein.pmid, None);
+        assert_eq!(
+            serde_json::to_value(&ein).unwrap(),
+            serde_json::json!({"kind": "EIN", "citation": "Blood: 2021;137(4):572"})
+        );
+
+        let ddin = Relation::parse(&Tag::DatasetDescribedIn, "Dataset described in: Sci Data. 2022;9:1. PMID: 456").unwrap();
+        assert_eq!(ddin.citation_text, "Sci Data. 2022;9:1.");
+        assert_eq!(ddin.pmid.as_deref(), Some("456"));
+
+        assert_eq!(Relation::parse(&Tag::Title, "A title. PMID: 1"), None);
+    }
+}
@@ -1,154 +1,440 @@
+use crate::articleid::{ArticleId, IdKind};
+use crate::dates::{parse_history, parse_numeric_date};
+use crate::error::{Error, Location};
+use crate::language::bcp47;
+use crate::options::Schema;
 use crate::types::*;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Generates `Tag` and its code/description/repeatability tables from one list,
+/// so that the three can not drift apart
+macro_rules! medline_tags {
+    ($( $variant:ident => $code:literal, $description:literal, $repeatable:literal; )*) => {
+        /// MEDLINE/Pubmed data element (field) tag
+        ///
+        /// Reference: https://www.nlm.nih.gov/bsd/mms/medlineelements.html
+        /// Reference: https://pubmed.ncbi.nlm.nih.gov/help/#pubmed-format
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum Tag {
+            $(
+                #[doc = concat!("`", $code, "`: ", $description)]
+                $variant,
+            )*
+            /// Well-formed (1-4 character) tag that is not in the NLM element list
+            Unknown(String),
+        }
+
+        impl Tag {
+            /// All known tags (i.e. every variant except `Unknown`)
+            pub const ALL: &'static [Tag] = &[$(Tag::$variant),*];
+
+            /// Tag as it appears in nbib files, e.g. "PMID", "AU"
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Tag::$variant => $code,)*
+                    Tag::Unknown(code) => code,
+                }
+            }
+
+            /// NLM name for the element, e.g. "PubMed Unique Identifier"
+            pub fn description(&self) -> &'static str {
+                match self {
+                    $(Tag::$variant => $description,)*
+                    Tag::Unknown(_) => "Unknown",
+                }
+            }
+
+            /// Whether the tag may appear more than once in a record
+            ///
+            /// Unknown tags are assumed repeatable
+            pub fn is_repeatable(&self) -> bool {
+                match self {
+                    $(Tag::$variant => $repeatable,)*
+                    Tag::Unknown(_) => true,
+                }
+            }
+
+            fn from_code(code: &str) -> Tag {
+                match code {
+                    $($code => Tag::$variant,)*
+                    _ => Tag::Unknown(code.to_string()),
+                }
+            }
+        }
+    };
+}
+
+medline_tags! {
+    Abstract => "AB", "Abstract", false;
+    Affiliation => "AD", "Affiliation", true;
+    ArticleIdentifier => "AID", "Article Identifier", true;
+    Author => "AU", "Author", true;
+    AuthorIdentifier => "AUID", "Author Identifier", true;
+    BookTitle => "BTI", "Book Title", false;
+    CopyrightInformation => "CI", "Copyright Information", false;
+    CommentIn => "CIN", "Comment in", true;
+    CorporateAuthor => "CN", "Corporate Author", true;
+    ConflictOfInterest => "COIS", "Conflict of Interest Statement", false;
+    CommentOn => "CON", "Comment on", true;
+    CorrectedRepublishedFrom => "CRF", "Corrected and Republished from", true;
+    CorrectedRepublishedIn => "CRI", "Corrected and Republished in", true;
+    CreateDate => "CRDT", "Create Date", true;
+    CollectionTitle => "CTI", "Collection Title", true;
+    ContributionDate => "CTDT", "Contribution Date", false;
+    DateCreated => "DA", "Date Created", false;
+    DateCompleted => "DCOM", "Date Completed", false;
+    DatasetDescribedIn => "DDIN", "Dataset described in", true;
+    DateElectronicPublication => "DEP", "Date of Electronic Publication", false;
+    DatePublication => "DP", "Date of Publication", false;
+    DateRevised => "DRDT", "Date Revised", false;
+    DatasetUseReportedIn => "DRIN", "Dataset use reported in", true;
+    ExpressionOfConcernFor => "ECF", "Expression of Concern for", true;
+    ExpressionOfConcernIn => "ECI", "Expression of Concern in", true;
+    Editor => "ED", "Editor", true;
+    EntrezDate => "EDAT", "Entrez Date", false;
+    ErratumFor => "EFR", "Erratum for", true;
+    ErratumIn => "EIN", "Erratum in", true;
+    Edition => "EN", "Edition", false;
+    FullAuthor => "FAU", "Full Author", true;
+    FullEditor => "FED", "Full Editor Name", true;
+    FullInvestigator => "FIR", "Full Investigator Name", true;
+    FullPersonalNameSubject => "FPS", "Full Personal Name as Subject", true;
+    GeneralNote => "GN", "General Note", true;
+    GrantNumber => "GR", "Grant Number", true;
+    GeneSymbol => "GS", "Gene Symbol", true;
+    Issue => "IP", "Issue", false;
+    Investigator => "IR", "Investigator Name", true;
+    InvestigatorAffiliation => "IRAD", "Investigator Affiliation", true;
+    Issn => "IS", "ISSN", true;
+    Isbn => "ISBN", "ISBN", true;
+    NlmUniqueId => "JID", "NLM Unique ID", false;
+    JournalTitle => "JT", "Journal Title", false;
+    Language => "LA", "Language", true;
+    LocationIdentifier => "LID", "Location Identifier", true;
+    DateLastRevised => "LR", "Date Last Revised", false;
+    MeshTerms => "MH", "MeSH Terms", true;
+    MeshDate => "MHDA", "MeSH Date", false;
+    ManuscriptIdentifier => "MID", "Manuscript Identifier", true;
+    SubstanceName => "NM", "Substance Name", true;
+    OtherAbstract => "OAB", "Other Abstract", true;
+    OtherAbstractLanguage => "OABL", "Other Abstract Language", true;
+    OtherCopyright => "OCI", "Other Copyright Information", true;
+    OtherId => "OID", "Other ID", true;
+    OriginalReportIn => "ORI", "Original Report in", true;
+    OtherTerm => "OT", "Other Term", true;
+    OtherTermOwner => "OTO", "Other Term Owner", true;
+    Owner => "OWN", "Owner", false;
+    Publisher => "PB", "Publisher", false;
+    Pagination => "PG", "Pagination", false;
+    PublicationHistoryStatus => "PHST", "Publication History Status", true;
+    PlaceOfPublication => "PL", "Place of Publication", false;
+    PmcId => "PMC", "PubMed Central Identifier", false;
+    PmcRelease => "PMCR", "PubMed Central Release", false;
+    Pmid => "PMID", "PubMed Unique Identifier", false;
+    PartialRetractionIn => "PRIN", "Partial Retraction in", true;
+    PartialRetractionOf => "PROF", "Partial Retraction of", true;
+    PersonalNameSubject => "PS", "Personal Name as Subject", true;
+    PublicationStatus => "PST", "Publication Status", false;
+    PublicationType => "PT", "Publication Type", true;
+    PublishingModel => "PUBM", "Publishing Model", false;
+    NumberOfReferences => "RF", "Number of References", false;
+    RetractionIn => "RIN", "Retraction in", true;
+    RegistryNumber => "RN", "Registry Number/EC Number", true;
+    RetractionOf => "ROF", "Retraction of", true;
+    RepublishedFrom => "RPF", "Republished from", true;
+    RepublishedIn => "RPI", "Republished in", true;
+    RetractedRepublishedFrom => "RRF", "Retracted and Republished from", true;
+    RetractedRepublishedIn => "RRI", "Retracted and Republished in", true;
+    Subset => "SB", "Subset", true;
+    SpaceFlightMission => "SFM", "Space Flight Mission", true;
+    SecondarySourceId => "SI", "Secondary Source ID", true;
+    Source => "SO", "Source", false;
+    SummaryForPatientsIn => "SPIN", "Summary for Patients in", true;
+    Status => "STAT", "Status", false;
+    JournalTitleAbbreviation => "TA", "Journal Title Abbreviation", false;
+    Title => "TI", "Title", false;
+    TransliteratedTitle => "TT", "Transliterated Title", false;
+    UpdateIn => "UIN", "Update in", true;
+    UpdateOf => "UOF", "Update of", true;
+    Volume => "VI", "Volume", false;
+    VolumeTitle => "VTI", "Volume Title", false;
+}
+
+impl FromStr for Tag {
+    type Err = Error;
+
+    /// Known codes map to their variant; other 1-4 character codes to `Tag::Unknown`
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        if code.is_empty() || code.chars().count() > 4 {
+            return Err(Error::BadTag {
+                location: Location { tag: Some(code.to_string()), ..Default::default() },
+            });
+        }
+        Ok(Tag::from_code(code))
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Serialized as the tag code, e.g. "RIN"
+impl Serialize for Tag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
 
 /// Convert a MEDLINE/Pubmed nbib (RIS-like) tag into corresponding CSL tag/value
 ///
 /// The return type is a nullable algebreic type that supports ordinary types, name fields, and date fields
 /// Recognized but non-supported tags, and unrecognized tags both yield an empty result: CSLValue(null)
-/// On error, CSLValue(null) is also returned (TODO: consider expect package or similar)
+/// A tag that is not 1-4 characters yields `Error::BadTag`
+pub fn process_tag(tag: String, value: String) -> Result<CSLValue, Error> {
+    Ok(tag_to_csl(&tag.parse()?, value))
+}
+
+/// Convert a parsed MEDLINE/Pubmed tag and its value, targeting the given CSL schema
 ///
-/// Notes: the "note" field should only appear once; therefore the PMID and PMC (and a few others)
-/// field handling is problematic
-pub fn process_tag(tag: String, value: String) -> Result<CSLValue, String> {
-    if tag.is_empty() || tag.chars().count() > 4 {
-        return Err("MEDLINE/Pubmed nbib tags are 1-4 characters".into());
+/// Under `Schema::Csl102`, PMID and PMC become the native `PMID` and `PMCID`
+/// variables and DEP the native `available-date`; otherwise this is `tag_to_csl`
+pub fn tag_to_csl_schema(tag: &Tag, value: String, schema: Schema) -> CSLValue {
+    match (schema, tag) {
+        (Schema::Csl102, Tag::Pmid) => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+            key: "PMID".into(),
+            value,
+        }),
+        (Schema::Csl102, Tag::PmcId) => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+            key: "PMCID".into(),
+            value,
+        }),
+        (Schema::Csl102, Tag::DateElectronicPublication) => CSLValue::CSLDateField(
+            CSLDateField::with_numeric_date("available-date".into(), value),
+        ),
+        _ => tag_to_csl(tag, value),
     }
+}
 
-    match &*tag {
-        "AB" => Ok(CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+/// Convert a parsed MEDLINE/Pubmed tag and its value into corresponding CSL tag/value
+///
+/// PMID and PMC are stuffed into "note" fields, as CSL 1.0.1 has no variables for them;
+/// repeated notes are combined when the item is serialized (see `FieldMerge`)
+pub fn tag_to_csl(tag: &Tag, value: String) -> CSLValue {
+    match tag {
+        Tag::Abstract => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
             key: "abstract".into(),
             value,
-        })),
-        "PMID" => Ok(CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+        }),
+        Tag::Pmid => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
             key: "note".into(),
             value: format!("PMID: {}", value),
-        })),
-        "PMC" => Ok(CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+        }),
+        Tag::PmcId => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
             key: "note".into(),
             value: format!("PMCID: {}", value),
-        })),
+        }),
         // Manuscript Identifier (MID) TODO
-        "TI" => Ok(CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+        Tag::Title => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
             key: "title".into(),
             value,
-        })),
-        "VI" => Ok(CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+        }),
+        Tag::Volume => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
             key: "volume".into(),
             value,
-        })),
-        "IP" => Ok(CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+        }),
+        Tag::Issue => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
             key: "issue".into(),
             value,
-        })),
-        "PG" => Ok(CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+        }),
+        Tag::Pagination => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
             key: "page".into(),
             value,
-        })),
-        // TODO need to transform to ISO 8601 per CSL specs;
-        // medline looks like YYYY Mon DD
-        "DP" => Ok(CSLValue::CSLDateField(CSLDateField::with_raw(
+        }),
+        // medline looks like YYYY Mon DD; ranges and seasons also occur
+        // transformed to CSL date-parts where possible, otherwise kept raw
+        Tag::DatePublication => CSLValue::CSLDateField(CSLDateField::with_date(
             "issued".into(),
             value,
-        ))),
+        )),
 
-        "FAU" => Ok(CSLValue::CSLNameField(CSLNameField::with_name(
+        // DEP: date of electronic publication, YYYYMMDD
+        // CSL 1.0.1 has no `available-date` variable, so it is an extension date here
+        Tag::DateElectronicPublication => {
+            let date = CSLDateField::with_numeric_date("available-date".into(), value);
+            custom_date(date.key, date.dp)
+        }
+
+        // PHST: publication history, e.g. "2020/01/30 00:00 [received]"
+        //
+        // [received] corresponds to CSL "submitted"; CSL has no variables for the
+        // remaining milestones, so [accepted], [revised], [pubmed] are extension dates
+        Tag::PublicationHistoryStatus => match parse_history(&value) {
+            Some(("received", dp)) => CSLValue::CSLDateField(CSLDateField {
+                key: "submitted".into(),
+                dp,
+            }),
+            Some((status @ ("accepted" | "revised" | "pubmed"), dp)) => {
+                custom_date(format!("{}-date", status), dp)
+            }
+            _ => CSLValue::None,
+        },
+
+        // EDAT: date the record was added to PubMed
+        Tag::EntrezDate => match parse_numeric_date(&value) {
+            Some(dp) => custom_date("entrez-date".into(), dp),
+            None => CSLValue::None,
+        },
+
+        Tag::FullAuthor => CSLValue::CSLNameField(CSLNameField::with_name(
             "author".into(),
             value,
-        ))),
-        "AU" => Ok(CSLValue::CSLNameField(CSLNameField::with_name(
+        )),
+        Tag::Author => CSLValue::CSLNameField(CSLNameField::with_name(
             "author".into(),
             value,
-        ))),
-        "FED" => Ok(CSLValue::CSLNameField(CSLNameField::with_name(
+        )),
+        Tag::FullEditor => CSLValue::CSLNameField(CSLNameField::with_name(
             "editor".into(),
             value,
-        ))),
-        "ED" => Ok(CSLValue::CSLNameField(CSLNameField::with_name(
+        )),
+        Tag::Editor => CSLValue::CSLNameField(CSLNameField::with_name(
             "editor".into(),
             value,
-        ))),
+        )),
+        // Corporate/collective author, listed in its place among the personal authors;
+        // never split, as organization names may contain commas
+        Tag::CorporateAuthor => CSLValue::CSLNameField(CSLNameField::with_literal(
+            "author".into(),
+            value,
+        )),
+        // Investigators (collaborators, not authors); the role can be changed with
+        // `Options::investigator_role`
+        Tag::FullInvestigator => CSLValue::CSLNameField(CSLNameField::with_name(
+            "contributor".into(),
+            value,
+        )),
+        Tag::Investigator => CSLValue::CSLNameField(CSLNameField::with_name(
+            "contributor".into(),
+            value,
+        )),
 
         // This would typically be an ORCID
-        // CSL name-variable definition does not have designated place for author id
-        // https://github.com/citation-style-language/schema/blob/c2142118a0265dfcf7d66aa3328251bedcc66af2/schemas/input/csl-data.json#L463-L498
-        //
-        // Because this can appear in the middle of author lists, we will ignore it for now :/
-        // instead of returning a "note" field -- however, once we have "note" aggregation,
-        // and we can ensure it runs before author deduplication , OK to emit as a note
-        "AUID" => Ok(CSLValue::None),
-
-        // return CSL "language"
-        // TODO, MEDLINE/Pubmed uses 3 letter language code; does CSL specify 3 or 2 letter?a
-        // https://www.nlm.nih.gov/bsd/language_table.html
-        "LA" => Ok(CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+        // CSL name-variable definition does not have designated place for author id;
+        // it is attached to the preceding author by `MedlineRecord::to_csl`, as a name extension
+        Tag::AuthorIdentifier => CSLValue::None,
+
+        // return CSL "language", as BCP 47 ("eng" -> "en"); unknown codes pass through
+        // With several LA rows the first is kept (see `FieldMerge`); all are listed
+        // by `MedlineRecord::to_csl` in the extension field "languages"
+        Tag::Language => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
             key: "language".into(),
-            value,
-        })),
+            value: bcp47(&value).map_or(value, String::from),
+        }),
 
-        "SI" => Ok(CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+        // Also parsed per record into databank links (see `MedlineRecord::secondary_source_ids`)
+        Tag::SecondarySourceId => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
             key: "note".into(),
             value,
-        })),
+        }),
 
         // (GR) Grant Number
+        // No CSL variable; written as an extension by `MedlineRecord::to_csl`
 
         // PT: Publication Type
-        //
-        // This field describes the type of material that the article represents;
-        // it characterizes the nature of the information or the manner in which
-        // it is conveyed (e.g., Review, Letter, Retracted Publication, Clinical Trial).
-        // Records may contain more than one Publication Type, which are listed in alphabetical order.
-        //
-        // Almost all citations have one of these four basic, most frequently used
-        // Publication Types applied to them: Journal Article, Letter, Editorial, News.
-        // One of the above four Publication Types is applied to more than 99% of
-        // all citations indexed for MEDLINE.
-        //
-        // Reference: https://www.nlm.nih.gov/mesh/pubtypes.html
-        "PT" => {
-            if value == "Journal Article" {
-                // return "type": "article-journal"
-                // https://aurimasv.github.io/z2csl/typeMap.xml#map-journalArticle
-                Ok(CSLValue::CSLOrdinaryField(CSLOrdinaryField {
-                    key: "type".into(),
-                    value: "article-journal".into(),
-                }))
-            } else {
-                Ok(CSLValue::None)
-            }
-        },
+        // Records carry several; the CSL "type" is chosen per record (see `pubtypes`)
+        Tag::PublicationType => CSLValue::None,
 
-        "TA" => Ok(CSLValue::CSLOrdinaryField(CSLOrdinaryField{
+        Tag::JournalTitleAbbreviation => CSLValue::CSLOrdinaryField(CSLOrdinaryField{
             key: "container-title-short".into(),
             value,
-        })),
+        }),
 
-        "JT" => Ok(CSLValue::CSLOrdinaryField(CSLOrdinaryField{
+        Tag::JournalTitle => CSLValue::CSLOrdinaryField(CSLOrdinaryField{
             key: "container-title".into(),
             value,
-        })),
+        }),
 
-        "AID" => {
-            // if DOI, return CSL "DOI" , and strip trailing "[doi]"
-            match value.strip_suffix("[doi]") {
-                Some(v) => Ok(CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+        // Bookshelf records; BTI and PL are mapped per record (see `MedlineRecord::to_csl`)
+        Tag::CollectionTitle => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+            key: "collection-title".into(),
+            value,
+        }),
+        Tag::VolumeTitle => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+            key: "volume-title".into(),
+            value,
+        }),
+        Tag::Edition => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+            key: "edition".into(),
+            value,
+        }),
+        Tag::Publisher => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+            key: "publisher".into(),
+            value,
+        }),
+        Tag::Isbn => CSLValue::CSLOrdinaryField(CSLOrdinaryField {
+            key: "ISBN".into(),
+            value,
+        }),
+
+        // Other AID kinds, and LID, are mapped per record (URL, number, e-locator page)
+        Tag::ArticleIdentifier => {
+            let aid = ArticleId::parse(&value);
+            if aid.is(&IdKind::Doi) {
+                CSLValue::CSLOrdinaryField(CSLOrdinaryField {
                     key: "DOI".into(),
-                    value: v.to_string()
-                })),
-                None => Ok(CSLValue::None)
+                    value: aid.value,
+                })
+            } else {
+                CSLValue::None
             }
         },
 
-        _ => Ok(CSLValue::None),
+        _ => CSLValue::None,
     }
 }
 
+/// Extension (`custom`) date
+fn custom_date(key: String, dp: DateParts) -> CSLValue {
+    CSLValue::CSLCustomField(CSLCustomField::new(&key, dp))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_badtag() {
-        assert!(process_tag("XYZZY".into(), "val".into()).is_err());
+        match process_tag("XYZZY".into(), "val".into()) {
+            Err(Error::BadTag { location }) => assert_eq!(location.tag.as_deref(), Some("XYZZY")),
+            other => panic!("expected BadTag, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_tag_enum() {
+        assert_eq!("PMID".parse::<Tag>().unwrap(), Tag::Pmid);
+        assert_eq!("AU".parse::<Tag>().unwrap(), Tag::Author);
+        assert_eq!("XYZ".parse::<Tag>().unwrap(), Tag::Unknown("XYZ".into()));
+        assert!("".parse::<Tag>().is_err());
+        assert!("XYZZY".parse::<Tag>().is_err());
+
+        assert_eq!(Tag::Pmid.description(), "PubMed Unique Identifier");
+        assert!(!Tag::Pmid.is_repeatable());
+        assert!(Tag::MeshTerms.is_repeatable());
+        assert_eq!(Tag::Unknown("XYZ".into()).to_string(), "XYZ");
+
+        // every code round-trips, and no two variants share a code
+        for tag in Tag::ALL {
+            assert_eq!(&tag.as_str().parse::<Tag>().unwrap(), tag);
+        }
+        let mut codes = Tag::ALL.iter().map(Tag::as_str).collect::<Vec<&str>>();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), Tag::ALL.len());
     }
 
     #[test]
@@ -183,6 +469,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pmid_native() {
+        let res = CSLOrdinaryField {
+            key: "PMCID".into(),
+            value: "PMC7531576".into(),
+        };
+        assert_eq!(
+            tag_to_csl_schema(&Tag::PmcId, "PMC7531576".into(), Schema::Csl102),
+            CSLValue::CSLOrdinaryField(res)
+        );
+        assert_eq!(
+            tag_to_csl_schema(&Tag::Pmid, "12345".into(), Schema::Csl101),
+            tag_to_csl(&Tag::Pmid, "12345".into())
+        );
+    }
+
+    #[test]
+    fn test_corporate_author() {
+        let value = "Genomics England, Research Consortium";
+        let res = tag_to_csl(&Tag::CorporateAuthor, value.into());
+        assert_eq!(res.key(), Some("author"));
+        assert_eq!(res.np().unwrap().literal.as_deref(), Some(value));
+        assert_eq!(res.np().unwrap().family, None);
+        assert_eq!(res.np().unwrap().given, None);
+    }
+
     #[test]
     fn test_author() {
         let res = CSLNameField::with_name("author".into(), "Blachly, James S".into());
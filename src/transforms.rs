@@ -1,53 +1,68 @@
+use crate::error::{Error, Location};
+use crate::names::{initials, parse_short_name};
 use crate::tags::*;
 use crate::types::*;
-use super::groupby::GroupByItr;
 use serde_json::Value;
+use std::ops::Range;
 
 pub struct MergeMultiline<'a, I>
 where
     I: Iterator<Item = &'a str>,
 {
     range: I,
-    buf: Vec<String> // temporary buffer; holds rows that should be concat'd
+    buf: Vec<String>, // temporary buffer; holds rows that should be concat'd
+    pos: usize,       // number of rows consumed from `range`
+    start: usize,     // offset (within `range`) of the first row in `buf`
 }
 
-impl<'a, I: Iterator> Iterator for MergeMultiline<'a, I> 
+impl<'a, I> MergeMultiline<'a, I>
 where
     I: Iterator<Item = &'a str>,
 {
-    type Item = String;
+    /// Yield each merged row together with the 0-based offsets (within the input range)
+    /// of the lines it was merged from
+    pub fn with_offsets(mut self) -> impl Iterator<Item = (Range<usize>, String)> + 'a
+    where
+        I: 'a,
+    {
+        std::iter::from_fn(move || self.next_with_offset())
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    fn next_with_offset(&mut self) -> Option<(Range<usize>, String)> {
         
                                        // into a single element in `ret`
 
-        while let Some(row) = self.range.next() {
-            assert!(row.chars().count() > 4, "Malformed record of length <= 4");
-            if row.chars().nth(4).unwrap() == '-' && self.buf.is_empty() {
-                // buf ~= row.strip;
-                self.buf.push(row.trim().to_string());
-            } else if row.chars().nth(4).unwrap() == '-' && !self.buf.is_empty() {
-                let ret: String;
+        for row in self.range.by_ref() {
+            let offset = self.pos;
+            self.pos += 1;
+            // A whitespace-only line within an item continues it with nothing
+            if !self.buf.is_empty() && row.trim().is_empty() {
+                continue;
+            }
+            // Only an indented row continues the current item. Every other row
+            // starts a new item -- including malformed ones, and indented rows
+            // with nothing to continue -- so that `split_row` can reject it
+            let starts_item = self.buf.is_empty()
+                || !row.starts_with(char::is_whitespace);
+            if starts_item && self.buf.is_empty() {
+                // buf ~= row.stripRight;
+                self.buf.push(row.trim_end().to_string());
+                self.start = offset;
+            } else if starts_item {
                 // New record; buf may contain one or more rows
                 // merge (if applicable) buf and append to ret
-                if self.buf.len() == 1 {
-                    // New record immediately after prior single-line record
-                    ret = self.buf.pop().unwrap();
-                    self.buf.clear();
-                } else {
-                    // New record after prior multi-line record
-                    ret = self.buf.join(" "); // `trim()` removed trailing and leading spaces
-                    self.buf.clear();
-                }
+                // (`trim()` removed trailing and leading spaces)
+                let ret = self.buf.join(" ");
+                self.buf.clear();
+                let ret_lines = self.start..offset;
                 // then add current record to buf
-                self.buf.push(row.trim().to_string());
-                return Some(ret)
-            } else if row.chars().nth(4).unwrap() != '-' && !self.buf.is_empty() {
+                self.buf.push(row.trim_end().to_string());
+                self.start = offset;
+                return Some((ret_lines, ret))
+            } else {
                 // A multi-line continuation
                 //buf ~= row.strip;
                 self.buf.push(row.trim().to_string())
-            } else {
-                panic!("Invalid State");
             }
         }
 
@@ -55,17 +70,26 @@ where
         // but to be safe we must test it is nonempty before finally dumping it to ret
         if self.buf.is_empty() {
             // noop
-            return None
-        } else if self.buf.len() == 1 {
-            return Some(self.buf.pop().unwrap())
+            None
         } else {
             let ret = self.buf.join(" ");
             self.buf.clear();
-            return Some(ret)
+            Some((self.start..self.pos, ret))
         }
     }
 }
 
+impl<'a, I> Iterator for MergeMultiline<'a, I> 
+where
+    I: Iterator<Item = &'a str>,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_offset().map(|(_, row)| row)
+    }
+}
+
 /// Merge multi-line records from a range of strings lazily
 ///
 /// For example:
@@ -77,81 +101,107 @@ pub fn merge_multiline_items<'a, I>(range: I) -> MergeMultiline<'a, I>
 where
     I: Iterator<Item = &'a str>,
 {
-    MergeMultiline {range: range, buf: vec![]}
+    MergeMultiline {range, buf: vec![], pos: 0, start: 0}
+}
+
+/// Split a merged row into its tag and value
+///
+/// Format: "XXXX- The quick brown fox jumped..."
+/// where XXXX of length 1-4 and right-padded
+pub fn split_row(row: &str) -> Result<(Tag, String), Error> {
+    // the tag is readable whenever the '-' is in place, even if the rest of the row is not
+    let tag = Some(row.chars().take(4).collect::<String>().trim().to_string())
+        .filter(|tag| !tag.is_empty() && row.chars().nth(4) == Some('-'));
+    let malformed = |reason| Error::MalformedLine {
+        location: Location { text: row.to_string(), tag: tag.clone(), ..Default::default() },
+        reason,
+    };
+    if row.chars().count() < 7 {
+        return Err(malformed("char < 7"));
+    }
+    if row.chars().nth(4) != Some('-') {
+        return Err(malformed("5th char not '-'"));
+    }
+    if row.chars().nth(5) != Some(' ') {
+        return Err(malformed("6th char not ' '"));
+    }
+
+    let key = row
+        .chars()
+        .take(4)
+        .collect::<String>()
+        .trim_end()
+        .parse::<Tag>()
+        .map_err(|e| e.at_line(0, row))?;
+    let value = row.chars().skip(6).collect::<String>();
+
+    Ok((key, value))
+}
+
+/// Best-effort tag and value from a row that `split_row` rejected
+///
+/// Accepts a 1-4 character (upper case or digit) tag followed by '-' anywhere in
+/// the row, with any spacing, e.g. "PMID-12345" or "TI - Title"
+pub fn repair_row(row: &str) -> Option<(Tag, String)> {
+    let (key, value) = row.split_once('-')?;
+    let key = key.trim();
+    let value = value.trim();
+    let valid_key = (1..=4).contains(&key.chars().count())
+        && key.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+
+    if valid_key && !value.is_empty() {
+        Some((key.parse().ok()?, value.to_string()))
+    } else {
+        None
+    }
+}
+
+/// Convert a single merged row to a CSL-JSON item tag
+///
+/// Unrecognized tags yield `CSLValue::None`
+pub fn row_to_csl(row: &str) -> Result<CSLValue, Error> {
+    split_row(row).map(|(key, value)| tag_to_csl(&key, value))
 }
 
 /// Convert medline record (group of tags) to CSL-JSON item tags lazily
-pub fn medline_to_csl<'a, I>(range: I) -> impl Iterator<Item = Result<CSLValue, String>> 
+pub fn medline_to_csl<I>(range: I) -> impl Iterator<Item = Result<CSLValue, Error>> 
 where
     I: Iterator<Item = String>,
 {
-    range.map(|row|{
-        // Format: "XXXX- The quick brown fox jumped..."
-        // where XXXX of length 1-4 and right-padded
-        if row.chars().count() < 7 {
-            return Err("Malformed record (char < 7)".to_string());
-        }
-        if row.chars().nth(4).unwrap() != '-' {
-            return Err("Malformed record (5th char not '-')".to_string());
-        }
-        // TODO: Change the above to emit warning, and `continue`
-
-        let key = row
-            .chars()
-            .take(4)
-            .collect::<String>()
-            .trim_end()
-            .to_string();
-        let value = row.chars().skip(6).collect::<String>();
-
-        process_tag(key, value)
-    }).filter(|x| {
+    range.map(|row| row_to_csl(&row)).filter(|x| {
         match x {
-            Ok(x) => {
-                match x {
-                    CSLValue::None => false,
-                    _ => true
-                }
-            }
+            Ok(x) => !matches!(x, CSLValue::None),
             Err(_) => true
         }
     })
 }
 
 
-/// Merge author records when both FAU and AU appear for same author
-/// (or make best effort)
-///
-/// Takes a range of CSL tags/values (collectively, a complete record => rec)
-///
-/// TODO: support multiple types (author, editor)
-pub fn reduce_authors<I>(range: I) -> impl Iterator<Item = CSLValue> 
-where
-    I: Iterator<Item = CSLValue>,
-{
-    // let names = range.filter(|x| );
-
-    let names_grouped_by_type = range.group_by(|a, b| a.is_name() && b.is_name() && a.key() == b.key());
-
-    /*    auto reduced = namesGroupedByType
-        .map!(n => n.chunkBy!(
-            (a,b) => a.tryGetMember!"np".family.split(" ")[0] ==
-                     b.tryGetMember!"np".family.split(" ")[0])
-            .map!(y => y.takeOne)
-        ).joiner.joiner;
-    */
-    let grouped_by_family = names_grouped_by_type
-        .map(|n| n.group_by(
-                |a, b|  a.is_name() && b.is_name() && 
-                        a.np().unwrap().family.as_ref().unwrap().split(" ").nth(0) ==
-                        b.np().unwrap().family.as_ref().unwrap().split(" ").nth(0)
-                )
-        );
 
-    let reduced = grouped_by_family.map(|x| x.map(|y| y.into_iter().nth(0).unwrap()));
-    let reduced = reduced.flatten();
-    // `reduced` now contains deduplicated names
-    reduced
+
+/// Whether `short`, an abbreviated name as in AU ("Kautto EA"), names the same
+/// person as `full`, a full name as parsed from FAU ("Kautto", "Esko A")
+///
+/// Family names (with particles) must agree; the initials must begin the
+/// initials of the given names (older records abbreviate to at most two)
+pub fn abbreviates(full: &NameParts, short: &str) -> bool {
+    let short = parse_short_name(short);
+    let family = |np: &NameParts| {
+        [np.non_dropping_particle.as_deref(), np.family.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .to_lowercase()
+    };
+    if full.family.is_none() || family(full) != family(&short) {
+        return false;
+    }
+    match (short.given, full.given.as_deref()) {
+        (Some(i), Some(given)) => initials(given).starts_with(&i.replace(' ', "")),
+        (None, None) => true,
+        _ => false,
+    }
 }
 
 /// Convert range of records (where each record is a range of tags)
@@ -161,35 +211,42 @@ where
     T: Iterator<Item = CSLValue>,
     I: Iterator<Item = T>,
 {
-    range.map(|rec| {
-        let mut item = CSLItem::new();
-
-        // Load the CSLItem by field type
-        for v in rec {
-            match v {
-            CSLValue::None => (),
-            CSLValue::CSLOrdinaryField(x) => item.fields.push(x),
-            CSLValue::CSLNameField(x) => item.names.push(x),
-            CSLValue::CSLDateField(x) => item.dates.push(x)
-            }
+    range.map(into_csl_item)
+}
+
+/// Convert a single record (range of tags) to a CSLItem
+pub fn into_csl_item<I>(rec: I) -> CSLItem
+where
+    I: Iterator<Item = CSLValue>,
+{
+    let mut item = CSLItem::new();
+
+    // Load the CSLItem by field type
+    for v in rec {
+        match v {
+        CSLValue::None => (),
+        CSLValue::CSLOrdinaryField(x) => item.fields.push(x),
+        CSLValue::CSLNameField(x) => item.names.push(x),
+        CSLValue::CSLDateField(x) => item.dates.push(x),
+        CSLValue::CSLCustomField(x) => item.custom.push(x),
         }
-        item
-    })
+    }
+    item
 }
 
 
 /// Convert range of records (where each record is a range of tags)
 /// to `asdf` (a binary JSON-like representation), which can then
 /// be serialized out to (non-pretty-printed) JSON
-pub fn to_json<I>(range: I) -> Result<Value, String>
+pub fn to_json<I>(range: I) -> Result<Value, Error>
 where
     I: Iterator<Item = CSLItem>,
 {
     let mut items = Vec::<Value>::new();
     for item in range {
-        items.push(serde_json::to_value(item).map_err(|e| e.to_string())?);
+        items.push(serde_json::to_value(item)?);
     }
-    return serde_json::to_value(items).map_err(|e| e.to_string());
+    Ok(serde_json::to_value(items)?)
 }
 
 
@@ -198,6 +255,19 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_split_row() {
+        let (tag, value) = split_row("TI  - Title").unwrap();
+        assert_eq!(tag, Tag::Title);
+        assert_eq!(value, "Title");
+
+        let tag = |row| split_row(row).unwrap_err().location().unwrap().tag.clone();
+        assert_eq!(tag("TI  -x").as_deref(), Some("TI"));
+        assert_eq!(tag("PMID-").as_deref(), Some("PMID"));
+        assert_eq!(tag("TI    Title without separator"), None);
+        assert_eq!(tag("TI  -").as_deref(), Some("TI"));
+    }
+
     #[test]
     fn test_merge() {
         let rec = vec![
@@ -224,11 +294,13 @@ mod tests {
         let names: Vec<CSLValue> = csl.map(|x| x.unwrap()).filter(|x| x.is_name()).collect();
         assert_eq!(names.len(), 4);
 
-        let merged_rec = merge_multiline_items(rec.into_iter());
+        let mut record = crate::record::MedlineRecord::new();
+        for row in merge_multiline_items(rec.into_iter()) {
+            let (tag, value) = split_row(&row).unwrap();
+            record.push(tag, value, 0);
+        }
+        let names: Vec<CSLValue> = record.to_csl().names.into_iter().map(CSLValue::CSLNameField).collect();
 
-        let reduced = reduce_authors(medline_to_csl(merged_rec.into_iter()).map(|x| x.unwrap()));
-        let names: Vec<CSLValue> = reduced.filter(|x| x.is_name()).collect();
-    
         assert_eq!(names.len(), 2);
         assert_eq!(names[0].key().unwrap(), "author");
         assert_eq!(names[0].np().unwrap().family, Some(String::from("Blachly")));
@@ -1,5 +1,8 @@
+use crate::dates::{parse_medline_date, parse_numeric_date};
+use crate::names::parse_name;
 use serde::Serialize;
-use serde::ser::{Serializer, SerializeSeq, SerializeMap};
+use serde::ser::{Error as _, Serializer, SerializeMap};
+use std::borrow::Cow;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 /// CSL item record
@@ -9,17 +12,37 @@ use std::hash::{Hash, Hasher};
 /// A few transformations are made:
 ///     (1) `id` is injected (and any existing id, which shouldn't happen, is ignored)
 ///     (2) name field tags are aggregated and grouped by type (author, editor, etc.)
-///     (3) ???
+///     (3) non-standard (extension) fields are nested under a `custom` object
 ///
 /// The end result should be semantically-correct CSL-JSON
 ///
 /// Reference: https://citeproc-js.readthedocs.io/en/latest/csl-json/markup.html
 /// Reference: https://github.com/citation-style-language/schema/blob/master/schemas/input/csl-data.json
-#[derive(Hash)]
+#[derive(Debug)]
 pub struct CSLItem {
     pub fields: Vec<CSLOrdinaryField>,
     pub names: Vec<CSLNameField>,
     pub dates: Vec<CSLDateField>,
+    pub custom: Vec<CSLCustomField>,
+
+    /// How ordinary fields sharing a key (e.g. several `note`s) are written out
+    pub merge: FieldMerge,
+}
+
+/// The id is derived from content only; `merge` affects output, not identity
+impl Hash for CSLItem {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.fields.hash(state);
+        self.names.hash(state);
+        self.dates.hash(state);
+        self.custom.hash(state);
+    }
+}
+
+impl Default for CSLItem {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CSLItem {
@@ -27,9 +50,17 @@ impl CSLItem {
         Self {
             fields: Vec::new(),
             names: Vec::new(),
-            dates: Vec::new()
+            dates: Vec::new(),
+            custom: Vec::new(),
+            merge: FieldMerge::default(),
         }
     }
+
+    /// Add an extension field, serialized under the item's `custom` object
+    pub fn push_custom(&mut self, key: &str, value: impl Serialize) {
+        self.custom.push(CSLCustomField::new(key, value));
+    }
+
     fn calculate_id(&self) -> u64 {
         let mut s = DefaultHasher::new();
         self.hash(&mut s);
@@ -44,6 +75,87 @@ impl CSLItem {
         names.dedup();
         names
     }
+
+    /// Ordinary fields with repeated keys merged according to `merge`,
+    /// in order of each key's first appearance
+    ///
+    /// Err carries the key for which `MergePolicy::Error` was violated
+    pub fn merged_fields(&self) -> Result<Vec<(&str, Cow<'_, str>)>, &str> {
+        let mut merged: Vec<(&str, Cow<str>)> = Vec::new();
+        for f in &self.fields {
+            let Some(i) = merged.iter().position(|(k, _)| *k == f.key) else {
+                merged.push((&f.key, Cow::Borrowed(&f.value)));
+                continue;
+            };
+            match self.merge.policy(&f.key) {
+                MergePolicy::Join(sep) => {
+                    let joined = merged[i].1.to_mut();
+                    joined.push_str(sep);
+                    joined.push_str(&f.value);
+                },
+                MergePolicy::FirstWins => (),
+                MergePolicy::LastWins => merged[i].1 = Cow::Borrowed(&f.value),
+                MergePolicy::Error => return Err(&f.key),
+            }
+        }
+        Ok(merged)
+    }
+}
+
+/// What to do when an item has more than one ordinary field with the same key
+///
+/// JSON objects cannot repeat keys, so repeated fields must be combined
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Concatenate the values, in input order, with this separator
+    Join(String),
+    /// Keep the first value
+    FirstWins,
+    /// Keep the last value
+    LastWins,
+    /// Fail serialization
+    Error,
+}
+
+/// Per-key merge policies for repeated ordinary fields
+///
+/// By default `note`s are joined with newlines and any other repeated key
+/// keeps its first value
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldMerge {
+    /// Policy for keys without an override
+    pub default: MergePolicy,
+
+    /// `(key, policy)` overrides
+    pub keys: Vec<(String, MergePolicy)>,
+}
+
+impl Default for FieldMerge {
+    fn default() -> Self {
+        Self {
+            default: MergePolicy::FirstWins,
+            keys: vec![("note".into(), MergePolicy::Join("\n".into()))],
+        }
+    }
+}
+
+impl FieldMerge {
+    /// Same policy for every key
+    pub fn uniform(policy: MergePolicy) -> Self {
+        Self { default: policy, keys: Vec::new() }
+    }
+
+    /// Set the policy for one key, replacing any previous override
+    pub fn set(&mut self, key: &str, policy: MergePolicy) {
+        self.keys.retain(|(k, _)| k != key);
+        self.keys.push((key.into(), policy));
+    }
+
+    pub fn policy(&self, key: &str) -> &MergePolicy {
+        self.keys.iter()
+            .find(|(k, _)| k == key)
+            .map_or(&self.default, |(_, p)| p)
+    }
 }
 
 impl Serialize for CSLItem {
@@ -53,25 +165,31 @@ impl Serialize for CSLItem {
     {
         let mut map = serializer.serialize_map(None)?;
         map.serialize_entry("id", &format!("nbib-{}", self.calculate_id()))?;
-        for f in &self.fields {
-            if f.key == "id" {
+        let fields = self.merged_fields()
+            .map_err(|key| S::Error::custom(format!("repeated CSL field {:?}", key)))?;
+        for (key, value) in fields {
+            if key == "id" {
                 continue;    // already injected id
             }
-            //serializer.putKey(f.key);
-            //serializer.putValue(f.value);
-            map.serialize_entry(&f.key, &f.value)?;
+            map.serialize_entry(key, &value)?;
         }
         let types = self.name_types();
         for t in types {
-            let matchingNames = self.names.iter()
+            let matching_names = self.names.iter()
                 .filter(|a| a.key == t)
                 .map(|n| &n.np)
                 .collect::<Vec<&NameParts>>();
-            map.serialize_entry(t, &matchingNames)?;
+            map.serialize_entry(t, &matching_names)?;
         }
         for d in &self.dates {
             map.serialize_entry(&d.key, &d.dp)?;
         }
+        if !self.custom.is_empty() {
+            let custom = self.custom.iter()
+                .map(|c| (c.key.clone(), c.value.clone()))
+                .collect::<serde_json::Map<String, serde_json::Value>>();
+            map.serialize_entry("custom", &custom)?;
+        }
         map.end()
     }
 }
@@ -82,12 +200,15 @@ impl Serialize for CSLItem {
 /// We additionally allow None/null as signal for our conversion program taht
 /// either something went wrong or that a tag was ignored in conversion
 /// D: alias CSLValue = Nullable!(CSLOrdinaryField, CSLNameField, CSLDateField);
+///
+/// CSLCustomField carries data with no CSL variable (see CSL-JSON `custom`)
 #[derive(Clone, Debug, PartialEq)]
 pub enum CSLValue {
     None,
     CSLOrdinaryField(CSLOrdinaryField),
     CSLNameField(CSLNameField),
     CSLDateField(CSLDateField),
+    CSLCustomField(CSLCustomField),
 }
 
 impl CSLValue {
@@ -96,7 +217,8 @@ impl CSLValue {
             Self::None => false,
             Self::CSLOrdinaryField(_) => false,
             Self::CSLNameField(_) => true,
-            Self::CSLDateField(_) => false
+            Self::CSLDateField(_) => false,
+            Self::CSLCustomField(_) => false,
         }
     }
     pub fn key(&self) -> Option<&str> {
@@ -105,6 +227,7 @@ impl CSLValue {
             Self::CSLOrdinaryField(v) => Some(&v.key),
             Self::CSLNameField(v) => Some(&v.key),
             Self::CSLDateField(v) => Some(&v.key), 
+            Self::CSLCustomField(v) => Some(&v.key),
         }
     }
     pub fn np(&self) -> Option<&NameParts> {
@@ -113,6 +236,7 @@ impl CSLValue {
             Self::CSLOrdinaryField(_) => None,
             Self::CSLNameField(v) => Some(&v.np),
             Self::CSLDateField(_) => None,
+            Self::CSLCustomField(_) => None,
         }
     }
 }
@@ -140,28 +264,27 @@ pub struct CSLNameField {
 }
 
 impl CSLNameField {
+    /// Personal name, as "Family, Given" (FAU) or "Family Initials" (AU)
+    ///
+    /// Not for organizations, whose names may contain commas: see `with_literal`
     pub fn with_name(nametype: String, name: String) -> Self {
+        CSLNameField {
+            key: nametype,
+            full: name.contains(','),
+            np: parse_name(&name),
+        }
+    }
 
-        let name_parts: Vec<&str> = name.split(',').collect();
-        let mut np = NameParts::default();
-        match name_parts.len() {
-            0 => panic!(),
-            1 => np.family = Some(name.trim().to_string()),
-            2 => {
-                np.family = Some(name_parts[0].trim().to_string());
-                np.given = Some(name_parts[1].trim().to_string());
-            },
-            _ => {
-                // > 1 comma; perhaps from a suffix like ", Jr."
-                // TODO: Warn? 
-                np.family = Some(name.trim().to_string());
-            }
-        };
-
+    /// Name of an organization (e.g. CN "COVID-19 Genomics UK Consortium"),
+    /// kept whole as a CSL `literal`
+    pub fn with_literal(nametype: String, name: String) -> Self {
         CSLNameField {
             key: nametype,
-            full: name_parts.len() == 2,
-            np
+            full: true,
+            np: NameParts {
+                literal: Some(name.trim().to_string()),
+                ..Default::default()
+            },
         }
     }
 }
@@ -200,6 +323,30 @@ pub struct NameParts {
     #[serde(rename = "parse-names")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parse_names: Option<String>,
+
+    /// Data with no CSL name-variable property; omitted when empty
+    #[serde(skip_serializing_if = "NameExtensions::is_empty")]
+    pub custom: NameExtensions,
+}
+
+/// Extension data attached to a single name, serialized as the name's `custom` object
+///
+/// The CSL name-variable has no place for these, so `Options::strict_csl` drops them
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Hash)]
+pub struct NameExtensions {
+    /// Affiliations (AD) of this author, in input order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub affiliation: Vec<String>,
+
+    /// ORCID (AUID), as `https://orcid.org/...`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orcid: Option<String>,
+}
+
+impl NameExtensions {
+    pub fn is_empty(&self) -> bool {
+        self.affiliation.is_empty() && self.orcid.is_none()
+    }
 }
 
 /// CSL "date" field
@@ -214,6 +361,24 @@ pub struct CSLDateField {
 }
 
 impl CSLDateField {
+    /// Date from a MEDLINE date string (e.g. "2020 Dec"), as CSL date-parts
+    /// when it can be parsed, otherwise as a `raw` date
+    pub fn with_date(key: String, value: String) -> Self {
+        match parse_medline_date(&value) {
+            Some(dp) => CSLDateField { key, dp },
+            None => Self::with_raw(key, value),
+        }
+    }
+
+    /// Date from a numeric MEDLINE date (e.g. "20201002", "2020/10/13 06:00"),
+    /// as CSL date-parts when it can be parsed, otherwise as a `raw` date
+    pub fn with_numeric_date(key: String, value: String) -> Self {
+        match parse_numeric_date(&value) {
+            Some(dp) => CSLDateField { key, dp },
+            None => Self::with_raw(key, value),
+        }
+    }
+
     pub fn with_raw(key: String, raw: String) -> Self {
         let dp = DateParts { raw: Some(raw), ..Default::default() };
         CSLDateField{
@@ -223,18 +388,88 @@ impl CSLDateField {
     }
 }
 
+/// Embedded in CSLDateField
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Hash)]
 pub struct DateParts {
+    /// One `[year, month, day]` array (month and day optional),
+    /// or two for a range
+    #[serde(rename = "date-parts")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    date_parts: Option<String>,
+    pub date_parts: Option<Vec<Vec<u32>>>,
+    /// 1 spring, 2 summer, 3 autumn, 4 winter
     #[serde(skip_serializing_if = "Option::is_none")]
-    season: Option<String>,
+    pub season: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    circa: Option<String>,  // String, number, bool
+    pub circa: Option<String>,  // String, number, bool
     #[serde(skip_serializing_if = "Option::is_none")]
-    literal: Option<String>,
+    pub literal: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    raw: Option<String>,
+    pub raw: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    edtf: Option<String>,
-}
\ No newline at end of file
+    pub edtf: Option<String>,
+}
+
+/// Extension field, serialized under the item's `custom` object
+///
+/// The `custom` object is defined by CSL-JSON 1.0.2 for data that has no designated field
+/// Reference: https://github.com/citation-style-language/schema/blob/master/schemas/input/csl-data.json
+#[derive(Clone, Debug, PartialEq)]
+pub struct CSLCustomField {
+    pub key: String,
+
+    pub value: serde_json::Value,
+}
+
+impl CSLCustomField {
+    /// Extension field holding any serializable value
+    ///
+    /// Serialization cannot fail for the values stored here (no maps with non-string
+    /// keys); should it, the error message is kept in place of the value so that
+    /// the field is not lost silently
+    pub fn new(key: &str, value: impl Serialize) -> Self {
+        let value = serde_json::to_value(value)
+            .unwrap_or_else(|e| serde_json::Value::String(format!("serialization error: {}", e)));
+        CSLCustomField { key: key.into(), value }
+    }
+}
+
+/// `serde_json::Value` is not `Hash`; hash its (deterministic) serialization instead
+impl Hash for CSLCustomField {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+        self.value.to_string().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(value: &str) -> CSLOrdinaryField {
+        CSLOrdinaryField { key: "note".into(), value: value.into() }
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut item = CSLItem::new();
+        item.fields.push(note("PMID: 1"));
+        item.fields.push(CSLOrdinaryField { key: "title".into(), value: "T".into() });
+        item.fields.push(note("PMCID: PMC2"));
+
+        let json = serde_json::to_value(&item).unwrap();
+        assert_eq!(json["note"], "PMID: 1\nPMCID: PMC2");
+        assert_eq!(json["title"], "T");
+
+        let id = json["id"].clone();
+        item.merge.set("note", MergePolicy::LastWins);
+        let json = serde_json::to_value(&item).unwrap();
+        assert_eq!(json["note"], "PMCID: PMC2");
+        assert_eq!(json["id"], id);
+
+        item.merge = FieldMerge::uniform(MergePolicy::FirstWins);
+        assert_eq!(serde_json::to_value(&item).unwrap()["note"], "PMID: 1");
+
+        item.merge.set("note", MergePolicy::Error);
+        assert!(serde_json::to_value(&item).is_err());
+    }
+}